@@ -0,0 +1,90 @@
+use crate::sync::{Mutex, OnceLock};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::{
+    any::Any,
+    fmt::{self, Debug, Display},
+};
+
+/// Value attached to `Oof`.
+pub(crate) enum Attachment {
+    /// Debug string of a value that could not be stored, like a borrowed value.
+    Formatted(String),
+    /// Closure evaluated the first time the attachment is rendered.
+    Lazy(Lazy),
+    /// Value stored as is, which can be retrieved with `Oof::attachment::<T>()`.
+    Value(Box<dyn AttachmentValue>),
+}
+
+impl Attachment {
+    pub(crate) fn lazy<D, F>(f: F) -> Self
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        Self::Lazy(Lazy {
+            f: Mutex::new(Some(Box::new(move || f().to_string()))),
+            value: OnceLock::new(),
+        })
+    }
+
+    pub(crate) fn value<T: AttachmentValue>(value: T) -> Self {
+        Self::Value(Box::new(value))
+    }
+
+    pub(crate) fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        match self {
+            // `Box<dyn AttachmentValue>` is an `AttachmentValue` itself, so deref before `as_any`.
+            Self::Value(v) => v.as_ref().as_any().downcast_ref(),
+            Self::Formatted(_) | Self::Lazy(_) => None,
+        }
+    }
+}
+
+impl Display for Attachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Formatted(s) => Display::fmt(s, f),
+            Self::Lazy(l) => Display::fmt(l.get(), f),
+            Self::Value(v) => Debug::fmt(v, f),
+        }
+    }
+}
+
+impl Debug for Attachment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Formatted(s) => Debug::fmt(s, f),
+            Self::Lazy(l) => Debug::fmt(l.get(), f),
+            Self::Value(v) => Debug::fmt(v, f),
+        }
+    }
+}
+
+pub(crate) trait AttachmentValue: Any + Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + Debug + Send + Sync> AttachmentValue for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub(crate) struct Lazy {
+    f: Mutex<Option<Box<dyn FnOnce() -> String + Send>>>,
+    value: OnceLock<String>,
+}
+
+impl Lazy {
+    /// Evaluate the closure on the first call, and return the cached string afterwards.
+    pub(crate) fn get(&self) -> &str {
+        self.value.get_or_init(|| {
+            let f = self.f.lock().take();
+
+            f.map(|f| f()).unwrap_or_default()
+        })
+    }
+}
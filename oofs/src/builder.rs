@@ -1,20 +1,32 @@
 use crate::{
+    attachments::Attachment,
     context::{Context, OofGeneratedContext},
-    tags::Tags,
-    Oof, OofExt,
+    error::Error,
+    meta::Meta,
+    tags::{Severity, Tags},
+    Inner, Oof, OofExt,
 };
-use core::fmt;
-use std::{convert::Infallible, error::Error};
+use alloc::{borrow::Cow, boxed::Box, format, string::ToString, vec::Vec};
+use core::{convert::Infallible, fmt};
 
 #[cfg(feature = "location")]
 use crate::Location;
 
+#[cfg(any(feature = "backtrace", feature = "tracing"))]
+use crate::chain::Chain;
+#[cfg(feature = "backtrace")]
+use std::backtrace::{Backtrace, BacktraceStatus};
+#[cfg(feature = "tracing")]
+use tracing_error::{SpanTrace, SpanTraceStatus};
+
 #[derive(Debug)]
-pub struct OofBuilder<E: 'static + Send + Sync + Error = Infallible> {
+pub struct OofBuilder<E: 'static + Into<Box<dyn Error + Send + Sync>> = Infallible> {
     context: Context,
     source: Option<E>,
     tags: Tags,
-    attachments: Vec<String>,
+    attachments: Vec<Attachment>,
+    causes: Vec<Oof>,
+    meta: Meta,
     #[cfg(feature = "location")]
     location: Location,
 }
@@ -29,17 +41,22 @@ impl OofBuilder {
             location: Location::caller(),
             tags: Tags::new(),
             attachments: Vec::new(),
+            causes: Vec::new(),
+            meta: Meta::default(),
         }
     }
 
     pub(crate) fn with_source<E>(self, source: E) -> OofBuilder<E>
     where
-        E: 'static + Send + Sync + Error,
+        E: 'static + Into<Box<dyn Error + Send + Sync>>,
     {
         let Self {
             context,
             tags,
             attachments,
+            causes,
+            meta,
+            #[cfg(feature = "location")]
             location,
             ..
         } = self;
@@ -49,16 +66,29 @@ impl OofBuilder {
             context,
             tags,
             attachments,
+            causes,
+            meta,
             #[cfg(feature = "location")]
             location,
         }
     }
+
+    pub(crate) fn with_causes(mut self, causes: Vec<Oof>) -> Self {
+        self.causes = causes;
+        self
+    }
 }
 
 impl<E> OofBuilder<E>
 where
-    E: 'static + Send + Sync + Error,
+    E: 'static + Into<Box<dyn Error + Send + Sync>>,
 {
+    #[cfg(all(feature = "std", feature = "location"))]
+    pub(crate) fn with_location(mut self, location: Location) -> Self {
+        self.location = location;
+        self
+    }
+
     pub(crate) fn with_generated(mut self, context: OofGeneratedContext) -> Self {
         self.context = context.into();
         self
@@ -74,13 +104,18 @@ where
         self
     }
 
+    pub(crate) fn with_tag_value<T: 'static + Send + Sync>(mut self, value: T) -> Self {
+        self.tags.tag_with(value);
+        self
+    }
+
     pub(crate) fn with_tag_if<T, F>(self, f: F) -> Self
     where
         T: 'static,
         F: FnOnce(&E) -> bool,
     {
         if let Some(source) = &self.source {
-            if f(&source) {
+            if f(source) {
                 return self.with_tag::<T>();
             }
         }
@@ -93,37 +128,91 @@ where
         F: FnOnce(&E, &mut Tags),
     {
         if let Some(source) = &self.source {
-            f(&source, &mut self.tags);
+            f(source, &mut self.tags);
         }
 
         self
     }
 
     pub(crate) fn with_attachment<D: fmt::Debug>(mut self, debuggable: D) -> Self {
-        self.attachments.push(format!("{debuggable:?}"));
+        self.attachments
+            .push(Attachment::Formatted(format!("{debuggable:?}")));
         self
     }
 
     pub(crate) fn with_attachment_lazy<D: ToString, F: FnOnce() -> D>(mut self, f: F) -> Self {
-        self.attachments.push(f().to_string());
+        self.attachments
+            .push(Attachment::Formatted(f().to_string()));
+        self
+    }
+
+    pub(crate) fn with_attachment_deferred<D, F>(mut self, f: F) -> Self
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        self.attachments.push(Attachment::lazy(f));
+        self
+    }
+
+    pub(crate) fn with_attachment_value<V>(mut self, value: V) -> Self
+    where
+        V: 'static + Send + Sync + fmt::Debug,
+    {
+        self.attachments.push(Attachment::value(value));
+        self
+    }
+
+    pub(crate) fn with_code<C: Into<Cow<'static, str>>>(mut self, code: C) -> Self {
+        self.meta.code = Some(code.into());
+        self
+    }
+
+    pub(crate) fn with_severity(mut self, severity: Severity) -> Self {
+        self.meta.severity = Some(severity);
         self
     }
 
     pub(crate) fn build(self) -> Oof {
-        Oof {
-            source: self.source.map(Into::into),
-            context: Box::new(self.context),
+        // Boxed errors are converted as is, without boxing them again.
+        let source: Option<Box<dyn Error + Send + Sync>> = self.source.map(Into::into);
+
+        // Only the innermost `Oof` captures a backtrace and a span trace; outer ones would only repeat them.
+        #[cfg(any(feature = "backtrace", feature = "tracing"))]
+        let innermost = match &source {
+            Some(source) => !Chain::new(&**source).any(|e| e.is::<Oof>()),
+            // causes of `Oof::multi` have their own.
+            None => self.causes.is_empty(),
+        };
+
+        let meta = Meta {
+            #[cfg(feature = "backtrace")]
+            backtrace: innermost
+                .then(Backtrace::capture)
+                .filter(|b| b.status() == BacktraceStatus::Captured),
+            #[cfg(feature = "tracing")]
+            span_trace: innermost
+                .then(SpanTrace::capture)
+                .filter(|s| s.status() == SpanTraceStatus::CAPTURED),
+            ..self.meta
+        };
+
+        Oof::new(Inner {
+            source,
+            context: self.context,
             #[cfg(feature = "location")]
-            location: self.location,
+            location: Some(self.location),
             tags: self.tags,
             attachments: self.attachments,
-        }
+            causes: self.causes,
+            meta: meta.boxed(),
+        })
     }
 }
 
 impl<T, E> OofExt for Result<T, OofBuilder<E>>
 where
-    E: 'static + Send + Sync + Error,
+    E: 'static + Into<Box<dyn Error + Send + Sync>>,
 {
     type Return = T;
     type Error = E;
@@ -136,6 +225,10 @@ where
         self.map_err(|b| b.with_tag::<Tag>())
     }
 
+    fn _tag_with<Tag: 'static + Send + Sync>(self, value: Tag) -> Result<T, OofBuilder<E>> {
+        self.map_err(|b| b.with_tag_value(value))
+    }
+
     fn _tag_if<Tag, F>(self, f: F) -> Result<T, OofBuilder<E>>
     where
         Tag: 'static,
@@ -158,6 +251,29 @@ where
     fn _attach_lazy<D: ToString, F: FnOnce() -> D>(self, f: F) -> Result<T, OofBuilder<E>> {
         self.map_err(|b| b.with_attachment_lazy(f))
     }
+
+    fn _attach_deferred<D, F>(self, f: F) -> Result<T, OofBuilder<E>>
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        self.map_err(|b| b.with_attachment_deferred(f))
+    }
+
+    fn _attach_value<V>(self, value: V) -> Result<T, OofBuilder<E>>
+    where
+        V: 'static + Send + Sync + fmt::Debug,
+    {
+        self.map_err(|b| b.with_attachment_value(value))
+    }
+
+    fn _code<C: Into<Cow<'static, str>>>(self, code: C) -> Result<T, OofBuilder<E>> {
+        self.map_err(|b| b.with_code(code))
+    }
+
+    fn _severity(self, severity: Severity) -> Result<T, OofBuilder<E>> {
+        self.map_err(|b| b.with_severity(severity))
+    }
 }
 
 pub trait OofGenerator<T> {
@@ -166,7 +282,7 @@ pub trait OofGenerator<T> {
 
 impl<T, E> OofGenerator<T> for Result<T, OofBuilder<E>>
 where
-    E: 'static + Send + Sync + Error,
+    E: 'static + Into<Box<dyn Error + Send + Sync>>,
 {
     fn build_oof<F: FnOnce() -> OofGeneratedContext>(this: Self, f: F) -> Result<T, Oof> {
         match this {
@@ -184,7 +300,7 @@ where
 
 impl<T, E> OofGenerator<T> for Result<T, E>
 where
-    E: 'static + Send + Sync + Error,
+    E: 'static + Into<Box<dyn Error + Send + Sync>>,
 {
     #[cfg_attr(feature = "location", track_caller)]
     fn build_oof<F: FnOnce() -> OofGeneratedContext>(this: Self, f: F) -> Result<T, Oof> {
@@ -1,5 +1,6 @@
 use self::ChainState::*;
-use std::error::Error;
+use crate::{error::Error, Oof};
+use alloc::{vec, vec::Vec};
 
 #[derive(Clone)]
 pub(crate) struct Chain<'a> {
@@ -12,7 +13,7 @@ pub(crate) enum ChainState<'a> {
         next: Option<&'a (dyn Error + 'static)>,
     },
     Buffered {
-        rest: std::vec::IntoIter<&'a (dyn Error + 'static)>,
+        rest: vec::IntoIter<&'a (dyn Error + 'static)>,
     },
 }
 
@@ -89,3 +90,71 @@ impl Default for Chain<'_> {
         }
     }
 }
+
+/// Iterator over every error in the tree of an error, depth first, with the depth of each error.
+///
+/// Unlike [Chain], this also walks into every cause aggregated by `Oof::multi`, after the error they belong to.
+#[derive(Clone)]
+pub(crate) struct Tree<'a> {
+    state: TreeState<'a>,
+}
+
+#[derive(Clone)]
+enum TreeState<'a> {
+    Walking {
+        stack: Vec<(usize, &'a (dyn Error + 'static))>,
+    },
+    Buffered {
+        rest: vec::IntoIter<(usize, &'a (dyn Error + 'static))>,
+    },
+}
+
+impl<'a> Tree<'a> {
+    #[cold]
+    pub fn new(head: &'a (dyn Error + 'static)) -> Self {
+        Tree {
+            state: TreeState::Walking {
+                stack: vec![(0, head)],
+            },
+        }
+    }
+}
+
+impl<'a> Iterator for Tree<'a> {
+    type Item = (usize, &'a (dyn Error + 'static));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.state {
+            TreeState::Walking { stack } => {
+                let (depth, error) = stack.pop()?;
+
+                if let Some(oof) = error.downcast_ref::<Oof>() {
+                    let causes = oof.causes().iter().rev();
+                    stack.extend(causes.map(|c| (depth + 1, c as &(dyn Error + 'static))));
+                }
+                if let Some(source) = error.source() {
+                    stack.push((depth + 1, source));
+                }
+
+                Some((depth, error))
+            }
+            TreeState::Buffered { rest } => rest.next(),
+        }
+    }
+}
+
+impl DoubleEndedIterator for Tree<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let TreeState::Walking { .. } = self.state {
+            let rest: Vec<_> = self.by_ref().collect();
+            self.state = TreeState::Buffered {
+                rest: rest.into_iter(),
+            };
+        }
+
+        match &mut self.state {
+            TreeState::Buffered { rest } => rest.next_back(),
+            TreeState::Walking { .. } => None,
+        }
+    }
+}
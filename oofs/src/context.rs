@@ -1,18 +1,15 @@
+use crate::{report::Indented, var_check::__Snapshot};
+use alloc::{borrow::Cow, string::String, vec::Vec};
 use core::fmt::{self, Debug, Display, Write};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub(crate) enum Context {
     Generated(OofGeneratedContext),
     Custom(String),
+    #[default]
     None,
 }
 
-impl Default for Context {
-    fn default() -> Self {
-        Context::None
-    }
-}
-
 impl From<OofGeneratedContext> for Context {
     fn from(c: OofGeneratedContext) -> Self {
         Context::Generated(c)
@@ -33,50 +30,43 @@ impl Context {
     pub(crate) fn is_none(&self) -> bool {
         matches!(self, Context::None)
     }
-
-    pub(crate) fn is_custom(&self) -> bool {
-        matches!(self, Context::Custom(_))
-    }
-
-    pub(crate) fn is_generated(&self) -> bool {
-        matches!(self, Context::Generated(_))
-    }
 }
 
 #[derive(Debug, Clone)]
 pub struct OofGeneratedContext {
-    receiver: OofReceiver,
-    chain: Vec<OofMethod>,
-    returns_option: bool,
+    pub(crate) receiver: OofReceiver,
+    pub(crate) chain: Vec<OofMethod>,
+    pub(crate) returns_option: bool,
 }
 
 impl Display for OofGeneratedContext {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.receiver)?;
+        Display::fmt(&self.call(), f)?;
+        write!(f, "{}", self.outcome())
+    }
+}
+
+/// Call expression of a generated context, without its outcome.
+pub(crate) struct Call<'a>(&'a OofGeneratedContext);
 
-        let is_multiline = self.chain.len() > 2;
+impl Display for Call<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = self.0;
+        write!(f, "{}", c.receiver)?;
+
+        let is_multiline = c.chain.len() > 2;
         if is_multiline && !f.alternate() {
-            let mut indented = Indented {
-                inner: f,
-                number: None,
-                started: false,
-            };
+            let mut indented = Indented::new(f);
 
-            for method in &self.chain {
+            for method in &c.chain {
                 write!(indented, "\n.{method}")?;
             }
         } else {
-            for method in &self.chain {
+            for method in &c.chain {
                 write!(f, ".{method}")?;
             }
         }
 
-        if self.returns_option {
-            write!(f, " returned `None`")?;
-        } else {
-            write!(f, " failed")?;
-        }
-
         Ok(())
     }
 }
@@ -106,27 +96,26 @@ impl OofGeneratedContext {
     pub fn returns_option(&mut self) {
         self.returns_option = true;
     }
-}
 
-impl OofGeneratedContext {
-    pub fn fmt_args(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if self.receiver.args_exists() || self.chain.iter().any(|m| !m.args.is_empty()) {
-            writeln!(f, "\nParameters:")?;
-
-            let mut indented = Indented {
-                inner: f,
-                number: None,
-                started: false,
-            };
-
-            self.receiver.fmt_args(&mut indented)?;
+    pub(crate) fn call(&self) -> Call<'_> {
+        Call(self)
+    }
 
-            for method in &self.chain {
-                method.fmt_args(&mut indented)?;
-            }
+    pub(crate) fn outcome(&self) -> &'static str {
+        if self.returns_option {
+            " returned `None`"
+        } else {
+            " failed"
         }
+    }
+}
 
-        Ok(())
+impl OofGeneratedContext {
+    /// Arguments of the receiver and all methods in the chain.
+    pub fn args(&self) -> impl Iterator<Item = &OofArg> {
+        self.receiver
+            .args()
+            .chain(self.chain.iter().flat_map(|m| m.args.iter()))
     }
 }
 
@@ -174,34 +163,33 @@ impl OofReceiver {
         }
     }
 
-    pub fn fmt_args(&self, f: &mut impl Write) -> fmt::Result {
-        match self {
-            Self::Arg(a) => writeln!(f, "{a:#}"),
-            Self::Method(m) => m.fmt_args(f),
-            Self::Ident(_) => Ok(()),
-        }
+    pub fn args(&self) -> impl Iterator<Item = &OofArg> {
+        let args = match self {
+            Self::Arg(a) => core::slice::from_ref(a),
+            Self::Method(m) => m.args.as_slice(),
+            Self::Ident(_) => &[],
+        };
+
+        args.iter()
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct OofMethod {
-    is_async: bool,
-    name: &'static str,
-    args: Vec<OofArg>,
+    pub(crate) is_async: bool,
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) args: Vec<OofArg>,
 }
 
 impl Display for OofMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.name)?;
         write!(f, "(")?;
-        match self.args.as_slice() {
-            [rest @ .., last] => {
-                for arg in rest {
-                    write!(f, "{}, ", arg)?;
-                }
-                write!(f, "{}", last)?;
+        if let [rest @ .., last] = self.args.as_slice() {
+            for arg in rest {
+                write!(f, "{}, ", arg)?;
             }
-            [] => {}
+            write!(f, "{}", last)?;
         }
         write!(f, ")")?;
 
@@ -213,21 +201,11 @@ impl Display for OofMethod {
     }
 }
 
-impl OofMethod {
-    fn fmt_args(&self, f: &mut impl Write) -> fmt::Result {
-        for arg in &self.args {
-            writeln!(f, "{arg:#}")?;
-        }
-
-        Ok(())
-    }
-}
-
 impl OofMethod {
     pub fn new(is_async: bool, name: &'static str, args: Vec<OofArg>) -> OofMethod {
         Self {
             is_async,
-            name,
+            name: name.into(),
             args,
         }
     }
@@ -235,8 +213,8 @@ impl OofMethod {
 
 #[derive(Debug, Clone)]
 pub struct OofIdent {
-    name: &'static str,
-    is_async: bool,
+    pub(crate) name: Cow<'static, str>,
+    pub(crate) is_async: bool,
 }
 
 impl Display for OofIdent {
@@ -252,15 +230,18 @@ impl Display for OofIdent {
 
 impl OofIdent {
     pub fn new(is_async: bool, name: &'static str) -> OofIdent {
-        Self { name, is_async }
+        Self {
+            name: name.into(),
+            is_async,
+        }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct OofArg {
-    index: usize,
-    ty: &'static str,
-    display: Option<String>,
+    pub(crate) index: usize,
+    pub(crate) ty: Cow<'static, str>,
+    pub(crate) display: Option<ArgValue>,
 }
 
 impl Display for OofArg {
@@ -280,21 +261,53 @@ impl Display for OofArg {
 }
 
 impl OofArg {
-    pub fn new(index: usize, ty: &'static str, display: Option<String>) -> Self {
-        Self { index, ty, display }
+    pub fn new<D: Into<ArgValue>>(index: usize, ty: &'static str, display: Option<D>) -> Self {
+        Self {
+            index,
+            ty: ty.into(),
+            display: display.map(Into::into),
+        }
+    }
+}
+
+/// Debug string of an argument, or a primitive value to be formatted when displayed.
+#[derive(Debug, Clone)]
+pub enum ArgValue {
+    Formatted(String),
+    Snapshot(__Snapshot),
+}
+
+impl From<String> for ArgValue {
+    fn from(s: String) -> Self {
+        Self::Formatted(s)
+    }
+}
+
+impl From<__Snapshot> for ArgValue {
+    fn from(s: __Snapshot) -> Self {
+        Self::Snapshot(s)
+    }
+}
+
+impl Display for ArgValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Formatted(s) => Display::fmt(s, f),
+            Self::Snapshot(s) => Debug::fmt(s, f),
+        }
     }
 }
 
 #[cfg(feature = "location")]
 #[non_exhaustive]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Location {
     /// The file where the error was reported
-    file: &'static str,
+    pub(crate) file: Cow<'static, str>,
     /// The line where the error was reported
-    line: u32,
+    pub(crate) line: u32,
     /// The column where the error was reported
-    column: u32,
+    pub(crate) column: u32,
 }
 
 #[cfg(feature = "location")]
@@ -323,7 +336,21 @@ impl fmt::Display for Location {
 impl Location {
     /// Constructs a `Location` using the given information
     pub fn new(file: &'static str, line: u32, column: u32) -> Self {
-        Self { file, line, column }
+        Self {
+            file: file.into(),
+            line,
+            column,
+        }
+    }
+
+    /// Constructs a `Location` in a file only known at runtime, like the file of a panic or of a rebuilt `Oof`.
+    #[cfg(any(feature = "std", feature = "serde"))]
+    pub(crate) fn with_file(file: String, line: u32, column: u32) -> Self {
+        Self {
+            file: file.into(),
+            line,
+            column,
+        }
     }
 
     #[inline]
@@ -332,38 +359,19 @@ impl Location {
         let loc = core::panic::Location::caller();
         Self::new(loc.file(), loc.line(), loc.column())
     }
-}
 
-pub(crate) struct Indented<'a, D> {
-    pub(crate) inner: &'a mut D,
-    pub(crate) number: Option<usize>,
-    pub(crate) started: bool,
-}
-
-impl<T> Write for Indented<'_, T>
-where
-    T: Write,
-{
-    fn write_str(&mut self, s: &str) -> fmt::Result {
-        for (i, line) in s.split('\n').enumerate() {
-            if !self.started {
-                self.started = true;
-                match self.number {
-                    Some(number) => write!(self.inner, "{: >5}: ", number)?,
-                    None => self.inner.write_str("    ")?,
-                }
-            } else if i > 0 {
-                self.inner.write_char('\n')?;
-                if self.number.is_some() {
-                    self.inner.write_str("       ")?;
-                } else {
-                    self.inner.write_str("    ")?;
-                }
-            }
+    /// The file where the error was reported
+    pub fn file(&self) -> &str {
+        &self.file
+    }
 
-            self.inner.write_str(line)?;
-        }
+    /// The line where the error was reported
+    pub fn line(&self) -> u32 {
+        self.line
+    }
 
-        Ok(())
+    /// The column where the error was reported
+    pub fn column(&self) -> u32 {
+        self.column
     }
 }
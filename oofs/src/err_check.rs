@@ -0,0 +1,119 @@
+use crate::{
+    builder::OofBuilder,
+    error::Error,
+    panic::{payload_message, Panic},
+};
+use alloc::{boxed::Box, format, string::String};
+use core::{any::Any, fmt};
+
+/// Converts panic payloads, like the error of `JoinHandle::join()`, into errors tagged as `Panic`.
+///
+/// `#[oofs]` calls `(&&&&res).__oof_kind().into_source(res)` on the operand of `?`;
+/// this trait is implemented for `&&&Result<T, E>`, so it is picked before all the others.
+pub trait __ErrFromPanic {
+    #[inline]
+    fn __oof_kind(&self) -> __FromPanic {
+        __FromPanic
+    }
+}
+
+impl<T> __ErrFromPanic for &&&Result<T, Box<dyn Any + Send>> {}
+
+/// Keeps errors that convert into `Box<dyn Error + Send + Sync>` as they are.
+///
+/// This trait is implemented for `&&Result<T, E>`, so it is picked before `__ErrViaDisplay` and `__ErrViaDebug`.
+pub trait __ErrAsSource {
+    #[inline]
+    fn __oof_kind(&self) -> __AsSource {
+        __AsSource
+    }
+}
+
+impl<T, E> __ErrAsSource for &&Result<T, E> where E: 'static + Into<Box<dyn Error + Send + Sync>> {}
+impl<T, E> __ErrAsSource for &&Result<T, OofBuilder<E>> where
+    E: 'static + Into<Box<dyn Error + Send + Sync>>
+{
+}
+impl<T> __ErrAsSource for &&Option<T> {}
+
+/// Snapshots errors that are not `'static`, `Send`, `Sync` or `Error` through `Display`.
+pub trait __ErrViaDisplay {
+    #[inline]
+    fn __oof_kind(&self) -> __ViaDisplay {
+        __ViaDisplay
+    }
+}
+
+impl<T, E: fmt::Display> __ErrViaDisplay for &Result<T, E> {}
+
+/// Snapshots errors that do not implement `Display` through `Debug`, like `()`.
+pub trait __ErrViaDebug {
+    #[inline]
+    fn __oof_kind(&self) -> __ViaDebug {
+        __ViaDebug
+    }
+}
+
+impl<T, E: fmt::Debug> __ErrViaDebug for Result<T, E> {}
+
+pub struct __FromPanic;
+impl __FromPanic {
+    #[inline]
+    #[cfg_attr(feature = "location", track_caller)]
+    pub fn into_source<T>(
+        self,
+        res: Result<T, Box<dyn Any + Send>>,
+    ) -> Result<T, OofBuilder<__ErrSnapshot>> {
+        match res {
+            Ok(t) => Ok(t),
+            Err(payload) => Err(OofBuilder::new()
+                .with_source(__ErrSnapshot::new(payload_message(&*payload)))
+                .with_tag::<Panic>()),
+        }
+    }
+}
+
+pub struct __AsSource;
+impl __AsSource {
+    #[inline]
+    pub fn into_source<R>(self, res: R) -> R {
+        res
+    }
+}
+
+pub struct __ViaDisplay;
+impl __ViaDisplay {
+    #[inline]
+    pub fn into_source<T, E: fmt::Display>(self, res: Result<T, E>) -> Result<T, __ErrSnapshot> {
+        res.map_err(|e| __ErrSnapshot::new(format!("{e}")))
+    }
+}
+
+pub struct __ViaDebug;
+impl __ViaDebug {
+    #[inline]
+    pub fn into_source<T, E: fmt::Debug>(self, res: Result<T, E>) -> Result<T, __ErrSnapshot> {
+        res.map_err(|e| __ErrSnapshot::new(format!("{e:?}")))
+    }
+}
+
+/// Error formatted when it was returned, and used as the source of `Oof` in place of the original error.
+#[derive(Debug, Clone)]
+pub struct __ErrSnapshot {
+    message: String,
+}
+
+impl __ErrSnapshot {
+    #[cold]
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl fmt::Display for __ErrSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for __ErrSnapshot {}
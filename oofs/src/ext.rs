@@ -1,6 +1,10 @@
-use crate::{builder::OofBuilder, tags::Tags};
-use core::fmt;
-use std::{convert::Infallible, error::Error};
+use crate::{
+    builder::OofBuilder,
+    error::Error,
+    tags::{Severity, Tags},
+};
+use alloc::{borrow::Cow, boxed::Box, string::ToString};
+use core::{convert::Infallible, fmt};
 
 /// Helper trait for `Result` and `Option` to add tags and attach extra contexts.
 ///
@@ -24,7 +28,7 @@ use std::{convert::Infallible, error::Error};
 /// ```
 pub trait OofExt: Sized {
     type Return;
-    type Error: 'static + Send + Sync + Error;
+    type Error: 'static + Into<Box<dyn Error + Send + Sync>>;
 
     /// Build the error `Oof` with the given context, instead of using the generated context by attribute.
     fn _context<D: ToString>(self, context: D) -> Result<Self::Return, OofBuilder<Self::Error>>;
@@ -32,6 +36,33 @@ pub trait OofExt: Sized {
     /// Tag the given type that can be searched with `.tagged_nested::<T>()` in the higher level call.
     fn _tag<Tag: 'static>(self) -> Result<Self::Return, OofBuilder<Self::Error>>;
 
+    /// Tag the type of the given value, and keep the value to be retrieved with `.tag_value_nested::<T>()` in the higher level call.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// # use std::time::Duration;
+    /// struct RetryAfter(Duration);
+    ///
+    /// #[oofs]
+    /// fn fetch() -> Result<u64, Oof> {
+    ///     let ret = "hello world"
+    ///         .parse::<u64>()
+    ///         ._tag_with(RetryAfter(Duration::from_secs(1)))?;
+    ///
+    ///     Ok(ret)
+    /// }
+    ///
+    /// let err = fetch().unwrap_err();
+    ///
+    /// assert!(err.tagged_nested::<RetryAfter>());
+    /// assert_eq!(err.tag_value_nested::<RetryAfter>().unwrap().0, Duration::from_secs(1));
+    /// ```
+    fn _tag_with<Tag: 'static + Send + Sync>(
+        self,
+        value: Tag,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>>;
+
     /// Tag the given type if the closure evaluates to `true`.
     ///
     /// Closure provides the underlying source error, so that one can optionally use the source error to determine
@@ -54,6 +85,9 @@ pub trait OofExt: Sized {
     ///
     /// This attached value will be listed as attachments in the displayed error.
     ///
+    /// The value may be borrowed, like `._attach(&x)`, so only its debug string is kept, formatted right away;
+    /// use `_attach_value` to keep an owned value retrievable, or `_attach_deferred` to defer the work.
+    ///
     /// Ex)
     /// ```rust
     /// # use oofs::*;
@@ -91,6 +125,9 @@ pub trait OofExt: Sized {
     ///
     /// This attached value will be listed as attachments in the displayed error.
     ///
+    /// The closure is only called if there is an error, right away when the error is built, so it may borrow local values;
+    /// use `_attach_deferred` to call it only when the error is displayed.
+    ///
     /// Ex)
     /// ```rust
     /// # use oofs::*;
@@ -122,11 +159,91 @@ pub trait OofExt: Sized {
         self,
         f: F,
     ) -> Result<Self::Return, OofBuilder<Self::Error>>;
+
+    /// Attach the result of a closure that is called only when the error is displayed or serialized.
+    ///
+    /// The result is cached after the first call, and errors that are only inspected by their tags never call it.
+    /// Since the closure is stored in the error, it must be `'static + Send`; use a `move` closure to capture owned values.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// #[oofs]
+    /// fn parse(text: String) -> Result<u64, Oof> {
+    ///     let ret = "hello world"
+    ///         .parse::<u64>()
+    ///         ._attach_deferred(move || format!("while parsing {text:?}"))?;
+    ///
+    ///     Ok(ret)
+    /// }
+    ///
+    /// let err = parse("some input".to_owned()).unwrap_err();
+    ///
+    /// assert!(format!("{err:?}").contains("while parsing \"some input\""));
+    /// ```
+    fn _attach_deferred<D, F>(self, f: F) -> Result<Self::Return, OofBuilder<Self::Error>>
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D;
+
+    /// Attach a value that can be retrieved later with `Oof::attachment::<T>()` or `Oof::attachments_of::<T>()`.
+    ///
+    /// Unlike `_attach`, the value is stored as is instead of its debug string,
+    /// so it must be `'static + Send + Sync`. It is still listed as attachments in the displayed error.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// # use std::time::Duration;
+    /// #[oofs]
+    /// fn fetch() -> Result<u64, Oof> {
+    ///     let ret = "hello world"
+    ///         .parse::<u64>()
+    ///         ._attach_value(Duration::from_secs(5))?;
+    ///
+    ///     Ok(ret)
+    /// }
+    ///
+    /// let err = fetch().unwrap_err();
+    ///
+    /// assert_eq!(err.attachment::<Duration>(), Some(&Duration::from_secs(5)));
+    /// ```
+    fn _attach_value<V: 'static + Send + Sync + fmt::Debug>(
+        self,
+        value: V,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>>;
+
+    /// Set a stable error code, like `E-STORAGE-042`, that can be read with `Oof::code()` in the higher level call.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// #[oofs]
+    /// fn load() -> Result<Vec<u8>, Oof> {
+    ///     let ret = std::fs::read("does/not/exist")
+    ///         ._code("E-STORAGE-042")
+    ///         ._severity(Severity::Critical)?;
+    ///
+    ///     Ok(ret)
+    /// }
+    ///
+    /// let err = load().unwrap_err();
+    ///
+    /// assert_eq!(err.code(), Some("E-STORAGE-042"));
+    /// assert_eq!(err.severity(), Some(Severity::Critical));
+    /// ```
+    fn _code<C: Into<Cow<'static, str>>>(
+        self,
+        code: C,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>>;
+
+    /// Set the severity, that can be read with `Oof::severity()` in the higher level call.
+    fn _severity(self, severity: Severity) -> Result<Self::Return, OofBuilder<Self::Error>>;
 }
 
 impl<T, E> OofExt for Result<T, E>
 where
-    E: 'static + Send + Sync + Error,
+    E: 'static + Into<Box<dyn Error + Send + Sync>>,
 {
     type Return = T;
     type Error = E;
@@ -147,6 +264,17 @@ where
         }
     }
 
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _tag_with<Tag: 'static + Send + Sync>(
+        self,
+        value: Tag,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>> {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(OofBuilder::new().with_source(e).with_tag_value(value)),
+        }
+    }
+
     #[cfg_attr(feature = "location", track_caller)]
     fn _tag_if<Tag: 'static, F: FnOnce(&Self::Error) -> bool>(
         self,
@@ -190,6 +318,50 @@ where
             Err(e) => Err(OofBuilder::new().with_source(e).with_attachment_lazy(f)),
         }
     }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _attach_deferred<D, F>(self, f: F) -> Result<Self::Return, OofBuilder<Self::Error>>
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(OofBuilder::new().with_source(e).with_attachment_deferred(f)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _attach_value<V: 'static + Send + Sync + fmt::Debug>(
+        self,
+        value: V,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>> {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(OofBuilder::new()
+                .with_source(e)
+                .with_attachment_value(value)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _code<C: Into<Cow<'static, str>>>(
+        self,
+        code: C,
+    ) -> Result<Self::Return, OofBuilder<Self::Error>> {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(OofBuilder::new().with_source(e).with_code(code)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _severity(self, severity: Severity) -> Result<Self::Return, OofBuilder<Self::Error>> {
+        match self {
+            Ok(t) => Ok(t),
+            Err(e) => Err(OofBuilder::new().with_source(e).with_severity(severity)),
+        }
+    }
 }
 
 impl<T> OofExt for Option<T> {
@@ -212,6 +384,14 @@ impl<T> OofExt for Option<T> {
         }
     }
 
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _tag_with<Tag: 'static + Send + Sync>(self, value: Tag) -> Result<T, OofBuilder> {
+        match self {
+            Some(t) => Ok(t),
+            None => Err(OofBuilder::new().with_tag_value(value)),
+        }
+    }
+
     #[cfg_attr(feature = "location", track_caller)]
     fn _tag_if<Tag: 'static, F: FnOnce(&Self::Error) -> bool>(self, f: F) -> Result<T, OofBuilder> {
         match self {
@@ -246,4 +426,43 @@ impl<T> OofExt for Option<T> {
             None => Err(OofBuilder::new().with_attachment_lazy(f)),
         }
     }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _attach_deferred<D, F>(self, f: F) -> Result<T, OofBuilder>
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        match self {
+            Some(t) => Ok(t),
+            None => Err(OofBuilder::new().with_attachment_deferred(f)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _attach_value<V: 'static + Send + Sync + fmt::Debug>(
+        self,
+        value: V,
+    ) -> Result<T, OofBuilder> {
+        match self {
+            Some(t) => Ok(t),
+            None => Err(OofBuilder::new().with_attachment_value(value)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _code<C: Into<Cow<'static, str>>>(self, code: C) -> Result<T, OofBuilder> {
+        match self {
+            Some(t) => Ok(t),
+            None => Err(OofBuilder::new().with_code(code)),
+        }
+    }
+
+    #[cfg_attr(feature = "location", track_caller)]
+    fn _severity(self, severity: Severity) -> Result<T, OofBuilder> {
+        match self {
+            Some(t) => Ok(t),
+            None => Err(OofBuilder::new().with_severity(severity)),
+        }
+    }
 }
@@ -1,9 +1,34 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+// `OofBuilder` is returned by value from `OofExt` methods; only `Oof` itself is kept small.
+#![allow(clippy::result_large_err)]
+
+extern crate alloc;
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use attachments::Attachment;
 use builder::*;
 use context::*;
-use core::fmt::{self, Debug, Display, Write};
-use std::error::{self, Error};
+use core::fmt::{self, Debug, Display};
+use error::Error;
+use meta::Meta;
 use tags::Tags;
 
+// `core::error` is only stable since Rust 1.81, so `std` users keep using `std::error`.
+#[cfg(not(feature = "std"))]
+pub(crate) use core::error;
+#[cfg(feature = "std")]
+pub(crate) use std::error;
+
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTrace;
+
 #[cfg(all(
     feature = "debug_non_copyable_disabled",
     feature = "debug_non_copyable_full"
@@ -12,10 +37,33 @@ compile_error!(
     "features `debug_non_copyable_disabled` and `debug_non_copyable_full` are mutually exclusive"
 );
 
-pub type Result<T, E = Oof> = std::result::Result<T, E>;
+pub type Result<T, E = Oof> = core::result::Result<T, E>;
 
 pub use ext::OofExt;
+pub use multi::{OofIteratorExt, Validator};
 pub use oofs_derive::oofs;
+#[cfg(feature = "tag_registry")]
+pub use oofs_derive::Tag;
+pub use panic::Panic;
+pub use report::set_handler;
+pub use tags::{Severity, Tag, TagInfo};
+
+#[cfg(feature = "location")]
+pub use context::Location;
+#[cfg(feature = "std")]
+pub use panic::catch_unwind;
+#[cfg(feature = "std")]
+pub use report::{ExitCodes, ExitReport};
+#[cfg(feature = "http")]
+pub use response::{set_http_mapping, HttpMapping, HttpStatuses, PublicError, SetHttpMappingError};
+#[cfg(feature = "std")]
+pub use retry::{retry, retry_async, Attempt, RetryPolicy};
+#[cfg(feature = "serde")]
+pub use serialize::{RemoteError, SCHEMA_VERSION};
+#[cfg(feature = "serde")]
+pub use tags::register_tag;
+#[cfg(feature = "color")]
+pub use theme::{DisplayWith, Painted, Style, Theme};
 
 /// Create a custom error `Oof` similar to `anyhow!`
 ///
@@ -49,7 +97,7 @@ pub use oofs_derive::oofs;
 #[macro_export]
 macro_rules! oof {
     ($($arg:tt)*) => {
-        $crate::Oof::custom(format!($($arg)*))
+        $crate::Oof::custom($crate::__used_by_attribute::format!($($arg)*))
     };
 }
 
@@ -85,7 +133,7 @@ macro_rules! oof {
 /// # }
 /// ```
 ///
-/// Also, you can provide tags and attachments in braces.
+/// Also, you can provide tags, attachments, an error code and a severity in braces.
 ///
 /// Ex)
 /// ```rust
@@ -103,7 +151,9 @@ macro_rules! oof {
 /// ensure!(false, {
 ///   tag: [MyTag, OtherTag],
 ///   attach: [&y, "attachment", Instant::now()],
-///   attach_lazy: [|| format!("context {}", &z)]
+///   attach_lazy: [|| format!("context {}", &z)],
+///   code: "E-VALIDATION-001",
+///   severity: Warning
 /// });
 ///
 /// ensure!(false, "custom context with value {:?}", x, {
@@ -117,31 +167,53 @@ macro_rules! oof {
 #[macro_export]
 macro_rules! ensure {
     ($cond:expr $(, $($rest:tt)*)?) => {
-        $crate::ensure!(@fmt $cond, (), $($($rest)*)?);
+        $crate::ensure!(@fmt [return] $cond, (), $($($rest)*)?);
+    };
+    (@fmt $on:tt $cond:expr, (), $({ $($rest:tt)* })?) => {
+        $crate::ensure!(@meta $on $cond, $crate::oof!("assertion failed: `{}`", stringify!($cond)), $($($rest)*)?);
     };
-    (@fmt $cond:expr, (), $({ $($rest:tt)* })?) => {
-        $crate::ensure!(@meta $cond, $crate::oof!("assertion failed: `{}`", stringify!($cond)), $($($rest)*)?);
+    (@fmt $on:tt $cond:expr, ($($fmt:expr,)*), $({ $($rest:tt)* })?) => {
+        $crate::ensure!(@meta $on $cond, $crate::oof!($($fmt),*), $($($rest)*)?);
     };
-    (@fmt $cond:expr, ($($fmt:expr,)*), $({ $($rest:tt)* })?) => {
-        $crate::ensure!(@meta $cond, $crate::oof!($($fmt),*), $($($rest)*)?);
+    (@fmt $on:tt $cond:expr, ($($fmt:expr,)*), $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@fmt $on $cond, ($($fmt,)* $arg,), $($($rest)*)?);
     };
-    (@fmt $cond:expr, ($($fmt:expr,)*), $arg:expr $(, $($rest:tt)*)?) => {
-        $crate::ensure!(@fmt $cond, ($($fmt,)* $arg,), $($($rest)*)?);
+    (@meta $on:tt $cond:expr, $ret:expr, tag: [$($tag:tt)*] $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $crate::__used_by_attribute::__tags!(($ret) $($tag)*), $($($rest)*)?);
     };
-    (@meta $cond:expr, $ret:expr, tag: [$($tag:ty),* $(,)?] $(, $($rest:tt)*)?) => {
-        $crate::ensure!(@meta $cond, $ret $(.tag::<$tag>())*, $($($rest)*)?);
+    (@meta $on:tt $cond:expr, $ret:expr, attach: [$($a:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret $(.attach($a))*, $($($rest)*)?);
     };
-    (@meta $cond:expr, $ret:expr, attach: [$($a:expr),* $(,)?] $(, $($rest:tt)*)?) => {
-        $crate::ensure!(@meta $cond, $ret $(.attach($a))*, $($($rest)*)?);
+    (@meta $on:tt $cond:expr, $ret:expr, attach_lazy: [$($l:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret $(.attach_lazy($l))*, $($($rest)*)?);
     };
-    (@meta $cond:expr, $ret:expr, attach_lazy: [$($l:expr),* $(,)?] $(, $($rest:tt)*)?) => {
-        $crate::ensure!(@meta $cond, $ret $(.attach_lazy($l))*, $($($rest)*)?);
+    (@meta $on:tt $cond:expr, $ret:expr, attach_deferred: [$($l:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret $(.attach_deferred($l))*, $($($rest)*)?);
     };
-    (@meta $cond:expr, $ret:expr, ) => {
+    (@meta $on:tt $cond:expr, $ret:expr, attach_value: [$($v:expr),* $(,)?] $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret $(.attach_value($v))*, $($($rest)*)?);
+    };
+    (@meta $on:tt $cond:expr, $ret:expr, code: $code:expr $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret.with_code($code), $($($rest)*)?);
+    };
+    (@meta $on:tt $cond:expr, $ret:expr, severity: $severity:expr $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@meta $on $cond, $ret.with_severity({
+            #[allow(unused_imports)]
+            use $crate::Severity::*;
+            $severity
+        }), $($($rest)*)?);
+    };
+    (@meta [return] $cond:expr, $ret:expr, ) => {
         if !$cond {
             return $ret.into_res();
         }
     };
+    // used by `validate!`, which keeps the error instead of returning it.
+    (@meta [push $validator:expr] $cond:expr, $ret:expr, ) => {
+        if !$cond {
+            $validator.push($ret);
+        }
+    };
 }
 
 /// Check that two given expressions are same, else return an error.
@@ -198,7 +270,9 @@ macro_rules! ensure {
 /// ensure_eq!(1u8, 2u8, {
 ///   tag: [MyTag, OtherTag],
 ///   attach: [&y, "attachment", Instant::now()],
-///   attach_lazy: [|| format!("context {}", &z)]
+///   attach_lazy: [|| format!("context {}", &z)],
+///   code: "E-VALIDATION-002",
+///   severity: Error
 /// });
 ///
 /// ensure_eq!(1u8, 2u8, "custom context with value {:?}", x, {
@@ -216,8 +290,8 @@ macro_rules! ensure_eq {
             (left, right) => {
                 $crate::ensure!(*left == *right, "assertion failed: `(left == right)`", {
                     attach_lazy: [
-                        || format!(" left: {:?}", &*left),
-                        || format!("right: {:?}", &*right)
+                        || $crate::__used_by_attribute::format!(" left: {:?}", &*left),
+                        || $crate::__used_by_attribute::format!("right: {:?}", &*right)
                     ],
                     $($($rest)*)?
                 });
@@ -233,6 +307,39 @@ macro_rules! ensure_eq {
     };
 }
 
+/// Check that a given expression evaluates to `true`, else keep an error in the given [Validator](struct.Validator.html).
+///
+/// Unlike [ensure!](macro.ensure.html), this does not return; every failed check is kept,
+/// and [Validator::finish](struct.Validator.html#method.finish) returns all of them at once.
+/// Parameters after the validator are the same as for `ensure!`.
+///
+/// Ex)
+/// ```rust
+/// # use oofs::*;
+/// struct InvalidInput;
+///
+/// let (name, age) = ("", -1);
+/// let mut v = Validator::new();
+///
+/// validate!(v, !name.is_empty());
+/// validate!(v, age >= 0, "age {} is negative", age, {
+///   tag: [InvalidInput],
+///   code: "E-VALIDATION-003"
+/// });
+///
+/// let err = v.finish().unwrap_err();
+///
+/// assert_eq!(err.causes().len(), 2);
+/// assert!(err.causes()[0].to_string().starts_with("assertion failed: `!name.is_empty()`"));
+/// assert_eq!(err.causes()[1].code(), Some("E-VALIDATION-003"));
+/// ```
+#[macro_export]
+macro_rules! validate {
+    ($validator:expr, $cond:expr $(, $($rest:tt)*)?) => {
+        $crate::ensure!(@fmt [push $validator] $cond, (), $($($rest)*)?);
+    };
+}
+
 /// Wraps a custom error with `Oof`
 ///
 /// Ex)
@@ -265,108 +372,86 @@ macro_rules! ensure_eq {
 /// # }
 /// ```
 #[cfg_attr(feature = "location", track_caller)]
-pub fn wrap_err(e: impl 'static + Send + Sync + Error) -> Oof {
+pub fn wrap_err(e: impl 'static + Into<Box<dyn Error + Send + Sync>>) -> Oof {
     Oof::builder().with_source(e).build()
 }
 
 /// Error type for oofs.
 ///
 /// `Oof` implements `std::error::Error`.
+///
+/// `Oof` is a single pointer to its contents on the heap, so `Result<(), Oof>` is only as large as a pointer.
 pub struct Oof {
+    inner: Box<Inner>,
+}
+
+struct Inner {
     source: Option<Box<dyn 'static + Send + Sync + Error>>,
-    context: Box<Context>,
+    context: Context,
     tags: Tags,
-    attachments: Vec<String>,
+    attachments: Vec<Attachment>,
+    // errors aggregated by `Oof::multi`.
+    causes: Vec<Oof>,
+    meta: Option<Box<Meta>>,
     #[cfg(feature = "location")]
-    location: Location,
+    location: Option<Location>,
 }
 
-impl Display for Oof {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let context = self.context.as_ref();
-
-        write!(f, "{context}")?;
-
-        #[cfg(feature = "location")]
-        write!(f, " at `{}`", self.location)?;
-
-        if matches!(context, Context::Generated(_)) || !self.attachments.is_empty() {
-            writeln!(f)?;
-        }
-
-        if let Context::Generated(c) = context {
-            c.fmt_args(f)?;
-        }
-
-        if !self.attachments.is_empty() {
-            writeln!(f, "\nAttachments:")?;
-            for (i, a) in self.attachments.iter().enumerate() {
-                let mut indented = Indented {
-                    inner: f,
-                    number: Some(i),
-                    started: false,
-                };
-
-                write!(indented, "{}", a)?;
-                writeln!(f)?;
-            }
+impl Oof {
+    fn new(inner: Inner) -> Self {
+        Self {
+            inner: Box::new(inner),
         }
+    }
+}
 
-        Ok(())
+impl Display for Oof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        report::handler().display(&report::Frame::new(0, self, &report::DISPLAY), f)
     }
 }
 
 impl Debug for Oof {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if f.alternate() {
-            #[cfg(not(feature = "location"))]
-            let debug = f
-                .debug_struct("Oof")
-                .field("context", &self.context)
-                .field("source", &self.source)
-                .field("tags", &self.tags)
-                .field("attachments", &self.attachments)
-                .finish();
+            let mut debug = f.debug_struct("Oof");
 
-            #[cfg(feature = "location")]
-            let debug = f
-                .debug_struct("Oof")
-                .field("context", &self.context)
-                .field("source", &self.source)
-                .field("location", &self.location)
-                .field("tags", &self.tags)
-                .field("attachments", &self.attachments)
-                .finish();
-
-            return debug;
-        }
+            debug
+                .field("context", &self.inner.context)
+                .field("source", &self.inner.source);
 
-        write!(f, "{self}")?;
+            #[cfg(feature = "location")]
+            debug.field("location", &self.inner.location);
 
-        if let Some(cause) = self.source() {
-            write!(f, "\nCaused by:")?;
+            debug
+                .field("tags", &self.inner.tags)
+                .field("attachments", &self.inner.attachments)
+                .field("causes", &self.inner.causes)
+                .field("code", &self.own_code())
+                .field("severity", &self.own_severity());
 
-            let multiple = cause.source().is_some();
-            for (n, error) in chain::Chain::new(cause).enumerate() {
-                writeln!(f)?;
+            #[cfg(feature = "backtrace")]
+            debug.field(
+                "backtrace",
+                &self.inner.meta.as_ref().and_then(|m| m.backtrace.as_ref()),
+            );
 
-                let mut indented = Indented {
-                    inner: f,
-                    number: if multiple { Some(n) } else { None },
-                    started: false,
-                };
+            #[cfg(feature = "tracing")]
+            debug.field(
+                "span_trace",
+                &self.inner.meta.as_ref().and_then(|m| m.span_trace.as_ref()),
+            );
 
-                write!(indented, "{error}")?;
-            }
+            return debug.finish();
         }
 
-        Ok(())
+        report::handler().debug(report::Frames::new(self, report::debug(), true), f)
     }
 }
 
 impl error::Error for Oof {
     fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-        if let Some(e) = &self.source {
+        if let Some(e) = &self.inner.source {
             Some(e.as_ref())
         } else {
             None
@@ -383,6 +468,45 @@ impl Oof {
         Self::builder().with_custom(message).build()
     }
 
+    /// Aggregate multiple errors into one `Oof`, like failures of a validation or of a batch job.
+    ///
+    /// The errors are kept as sibling causes; the `Debug` report draws each of them as a branch under `Errors:`,
+    /// and [Oof::tagged_nested](struct.Oof.html#method.tagged_nested), [Oof::find_source](struct.Oof.html#method.find_source)
+    /// and the other methods looking through nested `Oof`s search every branch.
+    ///
+    /// Since `Error::source` returns a single error, the aggregated `Oof` has no source;
+    /// use [Oof::causes](struct.Oof.html#method.causes) to get the errors.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct InvalidInput;
+    ///
+    /// let err = Oof::multi([
+    ///     oof!("name is empty").tag::<InvalidInput>(),
+    ///     oof!("age is negative"),
+    /// ]);
+    ///
+    /// assert_eq!(err.causes().len(), 2);
+    /// assert!(err.to_string().starts_with("2 errors occurred"));
+    /// assert!(err.tagged_nested::<InvalidInput>());
+    /// ```
+    #[cfg_attr(feature = "location", track_caller)]
+    pub fn multi(errors: impl IntoIterator<Item = Oof>) -> Oof {
+        let causes: Vec<Oof> = errors.into_iter().collect();
+        let noun = if causes.len() == 1 { "error" } else { "errors" };
+
+        Self::builder()
+            .with_custom(alloc::format!("{} {noun} occurred", causes.len()))
+            .with_causes(causes)
+            .build()
+    }
+
+    /// Errors aggregated into this `Oof` by [Oof::multi](struct.Oof.html#method.multi); empty for other `Oof`s.
+    pub fn causes(&self) -> &[Oof] {
+        &self.inner.causes
+    }
+
     /// Wraps `Oof` in `Result::Err(_)`.
     ///
     /// Use it to easily return an `Err(Oof)` instead of manually wrapping it in `Err(_)`.
@@ -410,62 +534,344 @@ impl Oof {
         OofBuilder::new()
     }
 
+    /// Backtrace captured when the innermost `Oof` in the chain was built.
+    ///
+    /// Backtraces are captured only if enabled by environment variables `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`,
+    /// and only for the innermost `Oof`; outer `Oof`s return the backtrace of the innermost one.
+    ///
+    /// Backtraces are not serialized, so `Oof`s rebuilt from serialized reports do not have one.
+    #[cfg(feature = "backtrace")]
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        chain::Chain::new(self)
+            .filter_map(|e| e.downcast_ref::<Oof>())
+            .find_map(|e| e.inner.meta.as_ref()?.backtrace.as_ref())
+    }
+
+    /// Span trace of active `tracing` spans captured when the innermost `Oof` in the chain was built.
+    ///
+    /// Span traces are captured only if the subscriber has `tracing_error::ErrorLayer` installed,
+    /// and only for the innermost `Oof`; outer `Oof`s return the span trace of the innermost one.
+    ///
+    /// Span traces are not serialized, so `Oof`s rebuilt from serialized reports do not have one.
+    #[cfg(feature = "tracing")]
+    pub fn span_trace(&self) -> Option<&SpanTrace> {
+        chain::Chain::new(self)
+            .filter_map(|e| e.downcast_ref::<Oof>())
+            .find_map(|e| e.inner.meta.as_ref()?.span_trace.as_ref())
+    }
+
+    /// The last error in the chain of sources, or this `Oof` if it has no source.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// #[oofs]
+    /// fn outer() -> Result<u64, Oof> {
+    ///     Ok(inner()?)
+    /// }
+    ///
+    /// #[oofs]
+    /// fn inner() -> Result<u64, Oof> {
+    ///     Ok("hello world".parse::<u64>()?)
+    /// }
+    ///
+    /// let err = outer().unwrap_err();
+    ///
+    /// assert_eq!(err.root_cause().to_string(), "invalid digit found in string");
+    /// ```
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        chain::Chain::new(self).last().unwrap_or(self)
+    }
+
+    /// Get the first error of type `E` in the chain, starting from this `Oof` and looking through nested `Oof`s.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// use std::io;
+    ///
+    /// #[oofs]
+    /// fn outer() -> Result<(), Oof> {
+    ///     inner()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// #[oofs]
+    /// fn inner() -> Result<(), Oof> {
+    ///     std::fs::read("does/not/exist")?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = outer().unwrap_err();
+    ///
+    /// assert_eq!(err.downcast_ref::<io::Error>().unwrap().kind(), io::ErrorKind::NotFound);
+    /// ```
+    pub fn downcast_ref<E: 'static + Error>(&self) -> Option<&E> {
+        chain::Tree::new(self).find_map(|(_, e)| e.downcast_ref::<E>())
+    }
+
+    /// Get the first error of type `E` in the chain mutably, starting from this `Oof` and looking through nested `Oof`s.
+    ///
+    /// Like [Oof::downcast_ref](struct.Oof.html#method.downcast_ref), the source of each `Oof` is searched first,
+    /// then each of its causes; see [Oof::causes](struct.Oof.html#method.causes).
+    ///
+    /// `Error::source` only returns shared references, so sources of foreign errors are not searched;
+    /// use [Oof::downcast_ref](struct.Oof.html#method.downcast_ref) to look through them.
+    pub fn downcast_mut<E: 'static + Error>(&mut self) -> Option<&mut E> {
+        if (self as &dyn Error).is::<E>() {
+            return (self as &mut dyn Error).downcast_mut::<E>();
+        }
+
+        let inner = &mut *self.inner;
+
+        if let Some(source) = inner.source.as_deref_mut() {
+            if source.is::<E>() {
+                return source.downcast_mut::<E>();
+            }
+
+            if let Some(e) = source.downcast_mut::<Oof>().and_then(Oof::downcast_mut) {
+                return Some(e);
+            }
+        }
+
+        inner.causes.iter_mut().find_map(Oof::downcast_mut)
+    }
+
+    /// Get the first source of type `E`, skipping this `Oof` and looking through nested `Oof`s.
+    ///
+    /// Unlike [Oof::downcast_ref](struct.Oof.html#method.downcast_ref), this never returns this `Oof` itself,
+    /// so `find_source::<Oof>()` returns the nested `Oof`, if any.
+    pub fn find_source<E: 'static + Error>(&self) -> Option<&E> {
+        chain::Tree::new(self)
+            .skip(1)
+            .find_map(|(_, e)| e.downcast_ref::<E>())
+    }
+
+    /// Take the first source of type `E`, looking through nested `Oof`s.
+    ///
+    /// Like [Oof::downcast_mut](struct.Oof.html#method.downcast_mut), the source of each `Oof` is searched first,
+    /// then each of its causes. If no source is of type `E`, this `Oof` is returned as is.
+    ///
+    /// Since `Error::source` only returns references, sources of foreign errors cannot be taken.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// use std::{io, num::ParseIntError};
+    ///
+    /// #[oofs]
+    /// fn outer() -> Result<(), Oof> {
+    ///     inner()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// #[oofs]
+    /// fn inner() -> Result<(), Oof> {
+    ///     std::fs::read("does/not/exist")?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let err = outer().unwrap_err();
+    ///
+    /// let err = err.downcast::<ParseIntError>().unwrap_err();
+    /// let io_err: io::Error = err.downcast().unwrap();
+    ///
+    /// assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+    /// ```
+    pub fn downcast<E: 'static + Error>(mut self) -> Result<E, Oof> {
+        let source = match self.inner.source.take() {
+            Some(source) => source,
+            None => return self.downcast_cause(),
+        };
+
+        let source = match source.downcast::<E>() {
+            Ok(e) => return Ok(*e),
+            Err(source) => source,
+        };
+
+        self.inner.source = Some(match source.downcast::<Oof>() {
+            Ok(nested) => match nested.downcast::<E>() {
+                Ok(e) => return Ok(e),
+                Err(nested) => Box::new(nested),
+            },
+            Err(source) => source,
+        });
+
+        self.downcast_cause()
+    }
+
+    fn downcast_cause<E: 'static + Error>(mut self) -> Result<E, Oof> {
+        let causes = core::mem::take(&mut self.inner.causes);
+        let mut rest = Vec::with_capacity(causes.len());
+
+        for cause in causes {
+            match cause.downcast::<E>() {
+                Ok(e) => return Ok(e),
+                Err(cause) => rest.push(cause),
+            }
+        }
+
+        self.inner.causes = rest;
+
+        Err(self)
+    }
+
     /// Check if this `Oof` is tagged as given type.
     ///
+    /// Tags deriving [Tag](trait.Tag.html) also match their ancestors,
+    /// so an error tagged with `ConnectionReset` whose parent is `Transient` is also tagged as `Transient`.
+    ///
     /// This method only checks one level deep.
     /// To check all nested errors, use [Oof::tagged_nested](struct.Oof.html#method.tagged_nested).
     pub fn tagged<T: 'static>(&self) -> bool {
-        self.tags.tagged::<T>()
+        self.inner.tags.tagged::<T>()
     }
 
     /// Check if this `Oof` is tagged in all nested errors.
     ///
     /// This method checks all levels.
     pub fn tagged_nested<T: 'static>(&self) -> bool {
-        if self.tagged::<T>() {
-            return true;
-        }
-
-        for cause in chain::Chain::new(self).skip(1) {
-            if let Some(e) = cause.downcast_ref::<Oof>() {
-                if e.tagged::<T>() {
-                    return true;
-                }
-            }
-        }
-
-        false
+        self.find_tagged::<T>().is_some()
     }
 
     /// Check if this `Oof` is tagged in all nested errors in reverse order.
     ///
     /// This method checks all levels.
     pub fn tagged_nested_rev<T: 'static>(&self) -> bool {
-        for cause in chain::Chain::new(self).skip(1).rev() {
-            if let Some(e) = cause.downcast_ref::<Oof>() {
-                if e.tagged::<T>() {
-                    return true;
-                }
-            }
-        }
+        self.find_tagged_rev::<T>().is_some()
+    }
 
-        if self.tagged::<T>() {
-            return true;
-        }
+    /// Find the outermost `Oof` tagged as given type, starting from this `Oof`.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct RetryTag;
+    ///
+    /// #[oofs]
+    /// fn outer() -> Result<(), Oof> {
+    ///     inner()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// #[oofs]
+    /// fn inner() -> Result<u64, Oof> {
+    ///     Ok("hello world".parse::<u64>()._tag::<RetryTag>()._attach(123)?)
+    /// }
+    ///
+    /// let err = outer().unwrap_err();
+    /// let frame = err.find_tagged::<RetryTag>().unwrap();
+    ///
+    /// assert_eq!(frame.to_string(), err.find_source::<Oof>().unwrap().to_string());
+    /// assert_eq!(err.tagged_depth::<RetryTag>(), Some(1));
+    /// ```
+    pub fn find_tagged<T: 'static>(&self) -> Option<&Oof> {
+        self.frames_tagged::<T>().next().map(|(_, e)| e)
+    }
+
+    /// Find the innermost `Oof` tagged as given type.
+    pub fn find_tagged_rev<T: 'static>(&self) -> Option<&Oof> {
+        self.frames_tagged::<T>().next_back().map(|(_, e)| e)
+    }
 
-        false
+    /// Depth of the outermost `Oof` tagged as given type.
+    ///
+    /// Depth is the position in the chain of errors; `0` is this `Oof`, `1` is its source, and so on.
+    pub fn tagged_depth<T: 'static>(&self) -> Option<usize> {
+        self.frames_tagged::<T>().next().map(|(depth, _)| depth)
+    }
+
+    /// Iterate over every `Oof` in the chain tagged as given type, with its depth, starting from this `Oof`.
+    ///
+    /// Depth is the position in the chain of errors; `0` is this `Oof`, `1` is its source, and so on.
+    /// Causes aggregated by [Oof::multi](struct.Oof.html#method.multi) are one level deeper than the `Oof` holding them,
+    /// and each branch is iterated in order, depth first.
+    /// Iterate in reverse to start from the innermost one.
+    pub fn frames_tagged<T: 'static>(&self) -> impl DoubleEndedIterator<Item = (usize, &Oof)> {
+        chain::Tree::new(self)
+            .filter_map(|(depth, e)| Some((depth, e.downcast_ref::<Oof>()?)))
+            .filter(|(_, e)| e.tagged::<T>())
     }
 
     /// Tag `Oof` with type and return Self.
     pub fn tag<T: 'static>(mut self) -> Self {
-        self.tags.tag::<T>();
+        self.inner.tags.tag::<T>();
+        self
+    }
+
+    /// Tag `Oof` with the type of the given value, and keep the value to be retrieved with
+    /// [Oof::tag_value](struct.Oof.html#method.tag_value).
+    ///
+    /// `tagged::<T>()` also returns `true` for tags with values.
+    /// If already tagged with the same type, the value is replaced.
+    ///
+    /// Tag values are not serialized; deserialized `Oof`s keep only the tag.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct HttpStatus(u16);
+    ///
+    /// let err = oof!("custom error").tag_with(HttpStatus(503));
+    ///
+    /// assert!(err.tagged::<HttpStatus>());
+    /// assert_eq!(err.tag_value::<HttpStatus>().unwrap().0, 503);
+    /// ```
+    pub fn tag_with<T: 'static + Send + Sync>(mut self, value: T) -> Self {
+        self.inner.tags.tag_with(value);
         self
     }
 
+    /// Iterate over type names of tags in this `Oof`.
+    ///
+    /// Tags of a deserialized `Oof` that could not be resolved to a registered type are listed by their
+    /// serialized name.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct RetryTag;
+    ///
+    /// let err = oof!("custom error").tag::<RetryTag>();
+    ///
+    /// let names: Vec<_> = err.tag_names().collect();
+    ///
+    /// assert_eq!(names.len(), 1);
+    /// assert!(names[0].ends_with("RetryTag"));
+    /// ```
+    pub fn tag_names(&self) -> impl Iterator<Item = &str> {
+        self.inner.tags.names()
+    }
+
+    /// Iterate over metadata of tags in this `Oof` that implement [Tag](trait.Tag.html).
+    pub fn tag_infos(&self) -> impl Iterator<Item = &'static TagInfo> + '_ {
+        self.inner.tags.infos()
+    }
+
+    /// Get the value of the tag of type `T` in this `Oof`.
+    ///
+    /// This method only checks one level deep.
+    /// To check all nested errors, use [Oof::tag_value_nested](struct.Oof.html#method.tag_value_nested).
+    pub fn tag_value<T: 'static>(&self) -> Option<&T> {
+        self.inner.tags.value::<T>()
+    }
+
+    /// Get the value of the tag of type `T` in this `Oof` or nested `Oof`s, starting from the outermost one.
+    pub fn tag_value_nested<T: 'static>(&self) -> Option<&T> {
+        chain::Tree::new(self)
+            .filter_map(|(_, e)| e.downcast_ref::<Oof>())
+            .find_map(|e| e.tag_value::<T>())
+    }
+
     /// Attach any value that implements `std::fmt::Debug`.
     ///
     /// This attached value will be listed as attachments in the displayed error.
     ///
+    /// The value may be borrowed, so only its debug string is kept, formatted right away instead of when the error is displayed;
+    /// it cannot be read back with [Oof::attachment](struct.Oof.html#method.attachment).
+    /// Use [Oof::attach_value](struct.Oof.html#method.attach_value) to keep an owned value retrievable,
+    /// or [Oof::attach_deferred](struct.Oof.html#method.attach_deferred) to defer the work.
+    ///
     /// Ex)
     /// ```rust
     /// use oofs::{oof, oofs};
@@ -493,7 +899,9 @@ impl Oof {
     ///    1: "some attachment"
     /// ```
     pub fn attach<D: fmt::Debug>(mut self, debuggable: D) -> Self {
-        self.attachments.push(format!("{debuggable:?}"));
+        self.inner
+            .attachments
+            .push(Attachment::Formatted(alloc::format!("{debuggable:?}")));
         self
     }
 
@@ -501,6 +909,9 @@ impl Oof {
     ///
     /// This attached value will be listed as attachments in the displayed error.
     ///
+    /// The closure is called right away, so it may borrow local values;
+    /// use [Oof::attach_deferred](struct.Oof.html#method.attach_deferred) to call it only when the error is displayed.
+    ///
     /// Ex)
     /// ```rust
     /// use oofs::{oof, oofs};
@@ -525,21 +936,193 @@ impl Oof {
     ///    0: "some attachment"
     /// ```
     pub fn attach_lazy<D: ToString, F: FnOnce() -> D>(mut self, f: F) -> Self {
-        self.attachments.push(f().to_string());
+        self.inner
+            .attachments
+            .push(Attachment::Formatted(f().to_string()));
+        self
+    }
+
+    /// Attach the result of a closure that is called only when the error is displayed or serialized.
+    ///
+    /// The result is cached after the first call, so errors that are only handled by their tags never pay for it.
+    /// Since the closure is stored in the error, it must be `'static + Send`; use a `move` closure to capture owned values.
+    ///
+    /// Ex)
+    /// ```rust
+    /// use oofs::oof;
+    ///
+    /// let id = 42;
+    /// let err = oof!("custom error").attach_deferred(move || format!("request {id}"));
+    ///
+    /// assert!(format!("{err:?}").contains("0: request 42"));
+    /// ```
+    pub fn attach_deferred<D, F>(mut self, f: F) -> Self
+    where
+        D: ToString,
+        F: 'static + Send + FnOnce() -> D,
+    {
+        self.inner.attachments.push(Attachment::lazy(f));
+        self
+    }
+
+    /// Attach a value that can be retrieved later with [Oof::attachment](struct.Oof.html#method.attachment).
+    ///
+    /// Unlike [Oof::attach](struct.Oof.html#method.attach), the value is stored as is instead of its debug string,
+    /// so it must be `'static + Send + Sync`. It is still listed as attachments in the displayed error.
+    ///
+    /// Ex)
+    /// ```rust
+    /// use oofs::oof;
+    /// use std::time::Duration;
+    ///
+    /// let err = oof!("custom error").attach_value(Duration::from_secs(5));
+    ///
+    /// assert_eq!(err.attachment::<Duration>(), Some(&Duration::from_secs(5)));
+    /// ```
+    pub fn attach_value<T>(mut self, value: T) -> Self
+    where
+        T: 'static + Send + Sync + fmt::Debug,
+    {
+        self.inner.attachments.push(Attachment::value(value));
+        self
+    }
+
+    /// Get the first attached value of type `T`.
+    ///
+    /// This `Oof` is searched first, then all nested `Oof`s from the outermost one.
+    ///
+    /// Only values attached with `attach_value`, `_attach_value` or `attach_value: [..]` of `ensure!` can be retrieved;
+    /// values attached with `attach` or `attach_lazy` are kept only as strings.
+    pub fn attachment<T: 'static>(&self) -> Option<&T> {
+        self.attachments_of::<T>().next()
+    }
+
+    /// Iterate over all attached values of type `T` in this `Oof` and all nested `Oof`s.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct RequestId(u64);
+    /// # impl std::fmt::Debug for RequestId {
+    /// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { self.0.fmt(f) }
+    /// # }
+    ///
+    /// let err = oof!("custom error")
+    ///     .attach_value(RequestId(1))
+    ///     .attach_value(RequestId(2));
+    ///
+    /// let ids: Vec<u64> = err.attachments_of::<RequestId>().map(|r| r.0).collect();
+    ///
+    /// assert_eq!(ids, [1, 2]);
+    /// ```
+    pub fn attachments_of<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        chain::Tree::new(self)
+            .filter_map(|(_, e)| e.downcast_ref::<Oof>())
+            .flat_map(|e| {
+                e.inner
+                    .attachments
+                    .iter()
+                    .filter_map(Attachment::downcast_ref)
+            })
+    }
+
+    /// Set a stable error code, like `E-STORAGE-042`, and return Self.
+    ///
+    /// Ex)
+    /// ```rust
+    /// use oofs::{oof, Severity};
+    ///
+    /// let err = oof!("custom error")
+    ///     .with_code("E-STORAGE-042")
+    ///     .with_severity(Severity::Critical);
+    ///
+    /// assert_eq!(err.code(), Some("E-STORAGE-042"));
+    /// assert_eq!(err.severity(), Some(Severity::Critical));
+    /// assert!(format!("{err:?}").starts_with("critical[E-STORAGE-042]: custom error"));
+    /// ```
+    pub fn with_code<C: Into<Cow<'static, str>>>(mut self, code: C) -> Self {
+        self.inner.meta.get_or_insert_with(Default::default).code = Some(code.into());
         self
     }
+
+    /// Set the severity and return Self.
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.inner
+            .meta
+            .get_or_insert_with(Default::default)
+            .severity = Some(severity);
+        self
+    }
+
+    /// Error code of the outermost `Oof` in the chain that has one, starting from this `Oof`.
+    pub fn code(&self) -> Option<&str> {
+        self.oofs().find_map(Oof::own_code)
+    }
+
+    /// Error code of the innermost `Oof` in the chain that has one.
+    pub fn code_rev(&self) -> Option<&str> {
+        self.oofs().rev().find_map(Oof::own_code)
+    }
+
+    /// Severity of the outermost `Oof` in the chain that has one, starting from this `Oof`.
+    ///
+    /// An `Oof` without a severity set takes the highest severity of its tags deriving [Tag](trait.Tag.html), if any.
+    pub fn severity(&self) -> Option<Severity> {
+        self.oofs().find_map(Oof::own_severity)
+    }
+
+    /// Severity of the innermost `Oof` in the chain that has one.
+    pub fn severity_rev(&self) -> Option<Severity> {
+        self.oofs().rev().find_map(Oof::own_severity)
+    }
+
+    pub(crate) fn own_code(&self) -> Option<&str> {
+        self.inner.meta.as_ref()?.code.as_deref()
+    }
+
+    pub(crate) fn own_severity(&self) -> Option<Severity> {
+        match self.inner.meta.as_ref().and_then(|m| m.severity) {
+            Some(severity) => Some(severity),
+            None => self.tag_infos().filter_map(TagInfo::severity).max(),
+        }
+    }
+
+    fn oofs(&self) -> impl DoubleEndedIterator<Item = &Oof> {
+        chain::Chain::new(self).filter_map(|e| e.downcast_ref::<Oof>())
+    }
 }
 
+mod attachments;
 mod builder;
 mod chain;
 mod context;
+mod err_check;
 mod ext;
+mod meta;
+mod multi;
+mod panic;
+pub mod report;
+#[cfg(feature = "http")]
+mod response;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sync;
 mod tags;
+mod theme;
 mod var_check;
 
 /// Module used by attribute `#[oofs]`
 pub mod __used_by_attribute {
-    pub use crate::{builder::*, context::*, tags::*, var_check::*};
+    pub use crate::{builder::*, context::*, err_check::*, tags::*, var_check::*};
+    pub use alloc::{format, string::ToString, vec};
+    #[cfg(feature = "tag_registry")]
+    pub use inventory;
+    pub use oofs_derive::__tags;
+
+    #[cfg(feature = "std")]
+    pub use crate::panic::__catch_panics;
 
     pub const DEBUG_NON_COPYABLE: bool = cfg!(all(
         not(feature = "debug_non_copyable_disabled"),
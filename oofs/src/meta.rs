@@ -0,0 +1,33 @@
+use crate::tags::Severity;
+use alloc::{borrow::Cow, boxed::Box};
+
+#[cfg(feature = "backtrace")]
+use std::backtrace::Backtrace;
+#[cfg(feature = "tracing")]
+use tracing_error::SpanTrace;
+
+/// Values of `Oof` that are rarely set, boxed together to keep `Oof` small.
+#[derive(Debug, Default)]
+pub(crate) struct Meta {
+    pub(crate) code: Option<Cow<'static, str>>,
+    pub(crate) severity: Option<Severity>,
+    #[cfg(feature = "backtrace")]
+    pub(crate) backtrace: Option<Backtrace>,
+    #[cfg(feature = "tracing")]
+    pub(crate) span_trace: Option<SpanTrace>,
+}
+
+impl Meta {
+    /// Boxed only if any value is set.
+    pub(crate) fn boxed(self) -> Option<Box<Meta>> {
+        let empty = self.code.is_none() && self.severity.is_none();
+
+        #[cfg(feature = "backtrace")]
+        let empty = empty && self.backtrace.is_none();
+
+        #[cfg(feature = "tracing")]
+        let empty = empty && self.span_trace.is_none();
+
+        (!empty).then(|| Box::new(self))
+    }
+}
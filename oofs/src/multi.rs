@@ -0,0 +1,129 @@
+use crate::Oof;
+use alloc::vec::Vec;
+
+/// Accumulator of errors, to report every failed check at once instead of returning at the first one.
+///
+/// Use [validate!](macro.validate.html) to check conditions with the same syntax as [ensure!](macro.ensure.html),
+/// and [Validator::finish] to return all errors as one [Oof::multi](struct.Oof.html#method.multi).
+///
+/// Ex)
+/// ```rust
+/// # use oofs::*;
+/// struct InvalidInput;
+///
+/// #[oofs]
+/// fn parse_age(age: &str) -> Result<u8, Oof> {
+///     Ok(age.parse::<u8>()._tag::<InvalidInput>()?)
+/// }
+///
+/// #[oofs]
+/// fn validate_user(name: &str, age: &str) -> Result<(), Oof> {
+///     let mut v = Validator::new();
+///
+///     validate!(v, !name.is_empty(), "name is empty", { tag: [InvalidInput] });
+///     validate!(v, !age.starts_with('-'), "age {} is negative", age);
+///     let age = v.check(parse_age(age));
+///
+///     v.finish()?;
+///
+///     println!("{name} is {age:?} years old");
+///     Ok(())
+/// }
+///
+/// let err = validate_user("", "-1").unwrap_err();
+/// let causes = err.find_source::<Oof>().unwrap().causes();
+///
+/// assert_eq!(causes.len(), 3);
+/// assert!(causes[1].to_string().starts_with("age -1 is negative"));
+/// assert!(err.tagged_nested::<InvalidInput>());
+/// ```
+#[derive(Debug, Default)]
+pub struct Validator {
+    errors: Vec<Oof>,
+}
+
+impl Validator {
+    /// Validator without any error kept.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep the error.
+    pub fn push(&mut self, error: Oof) {
+        self.errors.push(error);
+    }
+
+    /// Keep the error of the result, if any, and return the value otherwise.
+    pub fn check<T>(&mut self, result: Result<T, Oof>) -> Option<T> {
+        match result {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.push(e);
+                None
+            }
+        }
+    }
+
+    /// Whether no error has been kept.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Errors kept so far.
+    pub fn errors(&self) -> &[Oof] {
+        &self.errors
+    }
+
+    /// `Ok(())` if no error has been kept, else all of them as one [Oof::multi](struct.Oof.html#method.multi).
+    #[cfg_attr(feature = "location", track_caller)]
+    pub fn finish(self) -> Result<(), Oof> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+
+        Err(Oof::multi(self.errors))
+    }
+}
+
+/// Helper trait for iterators of `Result`s to collect every error instead of stopping at the first one.
+///
+/// Ex)
+/// ```rust
+/// # use oofs::*;
+/// #[oofs]
+/// fn parse(text: &str) -> Result<u64, Oof> {
+///     Ok(text.parse::<u64>()?)
+/// }
+///
+/// let ok: Result<Vec<u64>, Oof> = ["1", "2"].into_iter().map(parse).collect_all();
+/// assert_eq!(ok.unwrap(), [1, 2]);
+///
+/// let err = ["1", "a", "b"].into_iter().map(parse).collect_all::<Vec<_>>().unwrap_err();
+/// assert_eq!(err.causes().len(), 2);
+/// ```
+pub trait OofIteratorExt<T>: Iterator<Item = Result<T, Oof>> + Sized {
+    /// Collect every `Ok` value into `C` if there is no `Err`,
+    /// else every `Err` into one [Oof::multi](struct.Oof.html#method.multi).
+    #[cfg_attr(feature = "location", track_caller)]
+    fn collect_all<C: FromIterator<T>>(self) -> Result<C, Oof> {
+        let mut errors = Vec::new();
+
+        let values = self
+            .filter_map(|res| match res {
+                Ok(t) => Some(t),
+                Err(e) => {
+                    errors.push(e);
+                    None
+                }
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(Oof::multi(errors))
+        }
+    }
+}
+
+impl<T, I: Iterator<Item = Result<T, Oof>>> OofIteratorExt<T> for I {}
@@ -0,0 +1,126 @@
+use crate::tags::{Severity, Tag};
+use alloc::string::String;
+use core::any::Any;
+
+#[cfg(feature = "std")]
+use crate::Oof;
+#[cfg(feature = "std")]
+use std::panic::{self, AssertUnwindSafe, UnwindSafe};
+
+/// Tag of errors converted from panics, by [catch_unwind], `#[oofs(catch_panics)]`,
+/// or `?` on the result of `JoinHandle::join()`.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{catch_unwind, Panic};
+///
+/// let err = catch_unwind(|| -> u64 { panic!("boom") }).unwrap_err();
+///
+/// assert!(err.tagged::<Panic>());
+/// ```
+pub struct Panic;
+
+impl Tag for Panic {
+    const NAME: &'static str = "Panic";
+    const DESCRIPTION: Option<&'static str> = Some("the code panicked");
+    const SEVERITY: Option<Severity> = Some(Severity::Critical);
+}
+
+/// Message of the panic, like `std` prints it.
+pub(crate) fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        String::from(*s)
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("Box<dyn Any>")
+    }
+}
+
+/// Invoke the closure, converting a panic into an `Oof` tagged as [Panic], like `std::panic::catch_unwind`.
+///
+/// The `Oof` has the panic message, and the location of the panic;
+/// to know the location, a panic hook is installed the first time this function is called,
+/// which calls the hook set before it. If another hook is set afterwards, the location of this call is used instead.
+///
+/// The default hook still prints the panic to stderr.
+///
+/// Ex)
+/// ```rust
+/// use oofs::catch_unwind;
+///
+/// let values: Vec<u64> = Vec::new();
+///
+/// let err = catch_unwind(|| values[3]).unwrap_err();
+///
+/// assert!(err.to_string().starts_with("panicked: index out of bounds"));
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "location", track_caller)]
+pub fn catch_unwind<T, F: FnOnce() -> T + UnwindSafe>(f: F) -> Result<T, Oof> {
+    #[cfg(feature = "location")]
+    location::install_hook();
+
+    let payload = match panic::catch_unwind(f) {
+        Ok(t) => return Ok(t),
+        Err(payload) => payload,
+    };
+
+    let b = Oof::builder()
+        .with_custom(alloc::format!("panicked: {}", payload_message(&*payload)))
+        .with_tag::<Panic>();
+
+    #[cfg(feature = "location")]
+    let b = match location::take() {
+        Some(location) => b.with_location(location),
+        None => b,
+    };
+
+    Err(b.build())
+}
+
+/// Used by `#[oofs(catch_panics)]`; unlike [catch_unwind], the function body is not required to be `UnwindSafe`.
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "location", track_caller)]
+pub fn __catch_panics<T, E: From<Oof>, F: FnOnce() -> Result<T, E>>(f: F) -> Result<T, E> {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(res) => res,
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(all(feature = "std", feature = "location"))]
+mod location {
+    use crate::Location;
+    use std::{cell::RefCell, panic, sync::Once};
+
+    std::thread_local! {
+        static LAST_PANIC: RefCell<Option<Location>> = const { RefCell::new(None) };
+    }
+
+    pub(super) fn install_hook() {
+        static HOOK: Once = Once::new();
+
+        HOOK.call_once(|| {
+            let prev = panic::take_hook();
+
+            panic::set_hook(Box::new(move |info| {
+                if let Some(loc) = info.location() {
+                    let location = Location::with_file(loc.file().into(), loc.line(), loc.column());
+
+                    // ignore panics while the thread local is being destroyed.
+                    let _ = LAST_PANIC.try_with(|last| last.replace(Some(location)));
+                }
+
+                prev(info);
+            }));
+        });
+
+        // a panic outside of `catch_unwind` may have left its location.
+        take();
+    }
+
+    pub(super) fn take() -> Option<Location> {
+        LAST_PANIC.try_with(|last| last.take()).ok().flatten()
+    }
+}
@@ -0,0 +1,731 @@
+use crate::{
+    attachments::Attachment,
+    chain::Chain,
+    context::{Context, OofArg, OofGeneratedContext},
+    error::Error,
+    sync::OnceLock,
+    Oof,
+};
+use alloc::{boxed::Box, string::ToString};
+use core::{
+    fmt::{self, Display, Write},
+    iter::Enumerate,
+};
+
+#[cfg(feature = "location")]
+use crate::context::Location;
+
+pub(crate) use options::{debug, DISPLAY};
+pub use options::{short_type_name, ReportOptions, ReportWith};
+
+#[cfg(feature = "std")]
+pub use exit::{ExitCodes, ExitReport};
+
+#[cfg(feature = "std")]
+mod exit;
+mod options;
+
+static HANDLER: OnceLock<Box<dyn ReportHandler>> = OnceLock::new();
+
+/// Renders `Oof` for its `Display` and `Debug` implementations.
+///
+/// Both methods have default implementations that produce the default report,
+/// so a handler only needs to override what it wants to change.
+/// If only [display](#method.display) is overridden, the default `debug` still renders every frame of the chain with it,
+/// numbered and indented under `Caused by:`.
+///
+/// [Indented](struct.Indented.html) provides the numbering and indentation used by the default report.
+/// With the `color` feature, each frame also carries the theme to render it in.
+///
+/// Ex)
+/// ```rust
+/// use oofs::report::{Frame, ReportHandler};
+/// use std::fmt;
+///
+/// struct OneLine;
+///
+/// impl ReportHandler for OneLine {
+///     fn display(&self, frame: &Frame<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "{}", frame.message())?;
+///         for attachment in frame.attachments() {
+///             write!(f, " [{attachment}]")?;
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// oofs::set_handler(OneLine).unwrap();
+///
+/// let err = oofs::oof!("custom error").attach(123u8);
+///
+/// assert_eq!(err.to_string(), "custom error [123]");
+/// ```
+pub trait ReportHandler: 'static + Send + Sync {
+    /// Render a single frame.
+    ///
+    /// This is used for `Display` of `Oof`, and for each frame of the `Debug` report.
+    fn display(&self, frame: &Frame<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = frame.options;
+        let theme = options.theme();
+
+        match frame.generated() {
+            Some(c) if options.compact() => {
+                write!(f, "{:#}{}", theme.call.paint(c.call()), c.outcome())?
+            }
+            Some(c) => write!(f, "{}{}", theme.call.paint(c.call()), c.outcome())?,
+            None if options.root_debug() && frame.oof.is_none() && frame.is_root() => {
+                write!(f, "{:?}", frame.error)?
+            }
+            None => write!(f, "{}", frame.message())?,
+        }
+
+        #[cfg(feature = "location")]
+        if let Some(location) = frame.location() {
+            write!(f, " at `{}`", theme.location.paint(location))?;
+        }
+
+        if options.compact() {
+            return Ok(());
+        }
+
+        let show_tags = frame.is_debug() && frame.tags().next().is_some();
+        let attachments = match options.attachments() {
+            true => frame.attachments().len(),
+            false => 0,
+        };
+
+        if frame.is_generated() || attachments > 0 || show_tags {
+            writeln!(f)?;
+        }
+
+        let mut parameters = frame.parameters().peekable();
+        if options.parameters() && parameters.peek().is_some() {
+            writeln!(f, "\n{}", theme.heading.paint("Parameters:"))?;
+
+            let mut indented = Indented::new(f);
+            for parameter in parameters {
+                let ty = theme.ty.paint(options.type_name(parameter.ty()));
+                write!(indented, "${}: {ty}", parameter.index())?;
+
+                if let Some(value) = parameter.value() {
+                    write!(indented, " = {}", theme.value.paint(value))?;
+                }
+
+                writeln!(indented)?;
+            }
+        }
+
+        if attachments > 0 {
+            writeln!(f, "\n{}", theme.heading.paint("Attachments:"))?;
+            for (i, attachment) in frame.attachments().enumerate() {
+                write!(Indented::numbered(f, i), "{attachment}")?;
+                writeln!(f)?;
+            }
+        }
+
+        if show_tags {
+            writeln!(f, "\n{}", theme.heading.paint("Tags:"))?;
+            for name in frame.tags() {
+                writeln!(f, "    {}", theme.tag.paint(options.type_name(name)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Render the whole chain of errors.
+    ///
+    /// This is used for `Debug` of `Oof` and for [Oof::report](../struct.Oof.html#method.report);
+    /// the alternate form `{:#?}` always prints the struct instead.
+    ///
+    /// The default report starts with the severity and the error code of the chain, if any, like `critical[E-STORAGE-042]: `.
+    ///
+    /// With [ReportOptions::with_collapse_repeats](struct.ReportOptions.html#method.with_collapse_repeats),
+    /// consecutive frames repeating the previous one, as in recursive calls, are collapsed into `[frame repeated N times]`.
+    ///
+    /// Causes aggregated by [Oof::multi](../struct.Oof.html#method.multi) are drawn as a tree under `Errors:`,
+    /// each of them rendered as a whole report of its own, numbered and indented under the `Oof` holding them.
+    /// The compact report lists them in brackets instead, like `2 errors occurred: [first; second]`.
+    fn debug(&self, mut frames: Frames<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let options = frames.options;
+        let theme = options.theme();
+
+        let total = frames.len();
+        let shown = options
+            .max_depth()
+            .map_or(total, |d| d.clamp(1, total.max(1)));
+        let omitted = total - shown;
+
+        let oof = frames.oof();
+        let mut frames = frames.by_ref().take(shown).peekable();
+        let merged = options.with_parameters(false);
+
+        let heading = theme.heading;
+        match (oof.severity(), oof.code()) {
+            (Some(severity), Some(code)) => {
+                write!(f, "{}: ", heading.paint(format_args!("{severity}[{code}]")))?
+            }
+            (Some(severity), None) => write!(f, "{}: ", heading.paint(severity))?,
+            (None, Some(code)) => write!(f, "{}: ", heading.paint(format_args!("[{code}]")))?,
+            (None, None) => {}
+        }
+
+        let Some(first) = frames.next() else {
+            return Ok(());
+        };
+
+        let causes = Causes(self, first.causes(), options);
+        let mut prev = merge(first, frames.peek(), &merged);
+        self.display(&prev, f)?;
+        write!(f, "{causes}")?;
+
+        let mut repeats = 0;
+
+        if options.compact() {
+            while let Some(frame) = frames.next() {
+                if options.collapse_repeats() && frame.is_repeat_of(&prev) {
+                    repeats += 1;
+                    continue;
+                }
+
+                if repeats > 0 {
+                    write!(f, ": {}", Repeated(repeats))?;
+                    repeats = 0;
+                }
+
+                let causes = Causes(self, frame.causes(), options);
+                prev = merge(frame, frames.peek(), &merged);
+                write!(f, ": {}{causes}", Rendered(self, &prev))?;
+            }
+
+            if repeats > 0 {
+                write!(f, ": {}", Repeated(repeats))?;
+            }
+
+            if omitted > 0 {
+                write!(f, ": ... {omitted} more {}", frames_noun(omitted))?;
+            }
+
+            return Ok(());
+        }
+
+        if total > 1 {
+            write!(f, "\n{}", theme.heading.paint("Caused by:"))?;
+
+            let multiple = total > 2;
+            while let Some(frame) = frames.next() {
+                if options.collapse_repeats() && frame.is_repeat_of(&prev) {
+                    repeats += 1;
+                    continue;
+                }
+
+                if repeats > 0 {
+                    writeln!(f)?;
+                    write!(Indented::new(f), "{}", Repeated(repeats))?;
+                    repeats = 0;
+                }
+
+                writeln!(f)?;
+
+                let mut indented = if multiple {
+                    Indented::numbered(f, frame.depth() - 1)
+                } else {
+                    Indented::new(f)
+                };
+
+                let causes = Causes(self, frame.causes(), options);
+                prev = merge(frame, frames.peek(), &merged);
+                write!(indented, "{}{causes}", Rendered(self, &prev))?;
+            }
+
+            if repeats > 0 {
+                writeln!(f)?;
+                write!(Indented::new(f), "{}", Repeated(repeats))?;
+            }
+
+            if omitted > 0 {
+                writeln!(f)?;
+                write!(
+                    Indented::new(f),
+                    "... {omitted} more {}",
+                    frames_noun(omitted)
+                )?;
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Some(span_trace) = oof.span_trace() {
+            let heading = theme.heading.paint("Span trace:");
+            write!(f, "\n\n{heading}\n{span_trace}")?;
+        }
+
+        #[cfg(feature = "backtrace")]
+        if let Some(backtrace) = oof.backtrace() {
+            let heading = theme.heading.paint("Stack backtrace:");
+            write!(f, "\n\n{heading}\n{backtrace}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn frames_noun(n: usize) -> &'static str {
+    if n == 1 {
+        "frame"
+    } else {
+        "frames"
+    }
+}
+
+/// Frame to render, without its parameters if they are merged into the next frame.
+fn merge<'a>(frame: Frame<'a>, next: Option<&Frame<'_>>, merged: &'a ReportOptions) -> Frame<'a> {
+    match next {
+        Some(next) if frame.options.merge_parameters() && frame.parameters_shown_in(next) => {
+            Frame {
+                options: merged,
+                ..frame
+            }
+        }
+        _ => frame,
+    }
+}
+
+/// Marker of collapsed frames, like `[frame repeated 12 times]`.
+struct Repeated(usize);
+
+impl Display for Repeated {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let times = if self.0 == 1 { "time" } else { "times" };
+        write!(f, "[frame repeated {} {times}]", self.0)
+    }
+}
+
+/// Handler producing the default report; used until another handler is set with [set_handler](fn.set_handler.html).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultHandler;
+
+impl ReportHandler for DefaultHandler {}
+
+/// Set the handler used to render every `Oof` in this process.
+///
+/// The handler can be set only once; later calls return an error and keep the first handler.
+pub fn set_handler(handler: impl ReportHandler) -> Result<(), SetHandlerError> {
+    HANDLER.set(Box::new(handler)).map_err(|_| SetHandlerError)
+}
+
+pub(crate) fn handler() -> &'static dyn ReportHandler {
+    match HANDLER.get() {
+        Some(handler) => handler.as_ref(),
+        None => &DefaultHandler,
+    }
+}
+
+/// Error returned by [set_handler](fn.set_handler.html) if a handler is already set.
+#[derive(Debug)]
+pub struct SetHandlerError;
+
+impl Display for SetHandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "report handler is already set")
+    }
+}
+
+impl Error for SetHandlerError {}
+
+/// Displays a frame with the given handler.
+struct Rendered<'a, H: ?Sized>(&'a H, &'a Frame<'a>);
+
+impl<H: ?Sized + ReportHandler> Display for Rendered<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.display(self.1, f)
+    }
+}
+
+/// Displays the reports of causes of a frame, aggregated by `Oof::multi`.
+struct Causes<'a, H: ?Sized>(&'a H, &'a [Oof], &'a ReportOptions);
+
+impl<H: ?Sized + ReportHandler> Display for Causes<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(handler, causes, options) = *self;
+
+        if causes.is_empty() {
+            return Ok(());
+        }
+
+        if options.compact() {
+            write!(f, ": [")?;
+            for (i, cause) in causes.iter().enumerate() {
+                if i > 0 {
+                    write!(f, "; ")?;
+                }
+                write!(
+                    f,
+                    "{}",
+                    Reported(handler, Frames::new(cause, options, true))
+                )?;
+            }
+
+            return write!(f, "]");
+        }
+
+        write!(f, "\n{}", options.theme().heading.paint("Errors:"))?;
+        for (i, cause) in causes.iter().enumerate() {
+            writeln!(f)?;
+            write!(
+                Indented::numbered(f, i),
+                "{}",
+                Reported(handler, Frames::new(cause, options, true))
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays the whole report of the frames with the given handler.
+struct Reported<'a, H: ?Sized>(&'a H, Frames<'a>);
+
+impl<H: ?Sized + ReportHandler> Display for Reported<'_, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.debug(self.1.clone(), f)
+    }
+}
+
+/// A single error in the chain of an `Oof`, as seen by [ReportHandler](trait.ReportHandler.html).
+///
+/// Frames of foreign errors only have a message; everything else is empty.
+#[derive(Clone, Copy)]
+pub struct Frame<'a> {
+    error: &'a (dyn Error + 'static),
+    oof: Option<&'a Oof>,
+    depth: usize,
+    is_debug: bool,
+    options: &'a ReportOptions,
+}
+
+impl<'a> Frame<'a> {
+    pub(crate) fn new(
+        depth: usize,
+        error: &'a (dyn Error + 'static),
+        options: &'a ReportOptions,
+    ) -> Self {
+        Self {
+            error,
+            oof: error.downcast_ref::<Oof>(),
+            depth,
+            is_debug: false,
+            options,
+        }
+    }
+
+    fn generated(&self) -> Option<&'a OofGeneratedContext> {
+        match &self.oof?.inner.context {
+            Context::Generated(c) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Position in the chain of errors; `0` is the outermost error, `1` is its source, and so on.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Whether the frame is rendered for the `Debug` report, which also lists tags, rather than for `Display`.
+    pub fn is_debug(&self) -> bool {
+        self.is_debug
+    }
+
+    /// The error of this frame.
+    pub fn error(&self) -> &'a (dyn Error + 'static) {
+        self.error
+    }
+
+    /// The error of this frame, if it is an `Oof`.
+    pub fn oof(&self) -> Option<&'a Oof> {
+        self.oof
+    }
+
+    /// Context of the `Oof`, or the message of a foreign error.
+    pub fn message(&self) -> &'a dyn Display {
+        match self.oof {
+            Some(oof) => &oof.inner.context,
+            None => self.error,
+        }
+    }
+
+    /// Whether the context was generated by `#[oofs]` rather than given as a custom message.
+    pub fn is_generated(&self) -> bool {
+        self.generated().is_some()
+    }
+
+    /// Whether this is the last error in the chain.
+    pub fn is_root(&self) -> bool {
+        self.error.source().is_none()
+    }
+
+    /// Whether this frame has the same message and location as the other one, like frames of recursive calls.
+    ///
+    /// Values of parameters are not compared, since they are what differs between recursive calls;
+    /// everything else shown in the frame must be the same, so that no tag, attachment, error code or severity is lost
+    /// by collapsing it. Frames of foreign errors and frames with causes aggregated by `Oof::multi` never repeat.
+    pub fn is_repeat_of(&self, other: &Frame<'_>) -> bool {
+        let (Some(oof), Some(other)) = (self.oof, other.oof) else {
+            return false;
+        };
+
+        #[cfg(feature = "location")]
+        if oof.inner.location != other.inner.location {
+            return false;
+        }
+
+        let (attachments, other_attachments) = (&oof.inner.attachments, &other.inner.attachments);
+        let same_attachments = attachments.len() == other_attachments.len()
+            && attachments
+                .iter()
+                .zip(other_attachments)
+                .all(|(a, b)| a.to_string() == b.to_string());
+
+        oof.inner.causes.is_empty()
+            && other.inner.causes.is_empty()
+            && oof.inner.tags.same_as(&other.inner.tags)
+            && oof.own_code() == other.own_code()
+            && oof.own_severity() == other.own_severity()
+            && same_attachments
+            && oof.inner.context.to_string() == other.inner.context.to_string()
+    }
+
+    /// Whether this frame has parameters, and the value of each one is also shown as a parameter of the other frame.
+    pub fn parameters_shown_in(&self, other: &Frame<'_>) -> bool {
+        let mut parameters = self.parameters().peekable();
+
+        parameters.peek().is_some()
+            && parameters.all(|p| {
+                let Some(value) = p.value().map(|v| v.to_string()) else {
+                    return false;
+                };
+
+                other
+                    .parameters()
+                    .any(|o| o.value().is_some_and(|o| o.to_string() == value))
+            })
+    }
+
+    /// Error code set on this `Oof`, if any.
+    pub fn code(&self) -> Option<&'a str> {
+        self.oof?.own_code()
+    }
+
+    /// Severity of this `Oof`, if any; see [Oof::severity](../struct.Oof.html#method.severity).
+    pub fn severity(&self) -> Option<crate::Severity> {
+        self.oof?.own_severity()
+    }
+
+    /// Options to render this frame with.
+    pub fn options(&self) -> &'a ReportOptions {
+        self.options
+    }
+
+    /// Theme to render this frame in.
+    #[cfg(feature = "color")]
+    pub fn theme(&self) -> &'a crate::Theme {
+        self.options.theme()
+    }
+
+    /// Location where the `Oof` was built.
+    #[cfg(feature = "location")]
+    pub fn location(&self) -> Option<&'a Location> {
+        self.oof?.inner.location.as_ref()
+    }
+
+    /// Parameters of the generated context.
+    pub fn parameters(&self) -> impl Iterator<Item = Parameter<'a>> {
+        self.generated()
+            .into_iter()
+            .flat_map(|c| c.args())
+            .map(Parameter)
+    }
+
+    /// Attachments of the `Oof`.
+    pub fn attachments(&self) -> impl ExactSizeIterator<Item = &'a dyn Display> {
+        let attachments: &'a [Attachment] = match self.oof {
+            Some(oof) => &oof.inner.attachments,
+            None => &[],
+        };
+
+        attachments.iter().map(|a| a as &dyn Display)
+    }
+
+    /// Errors aggregated into the `Oof` by [Oof::multi](../struct.Oof.html#method.multi).
+    pub fn causes(&self) -> &'a [Oof] {
+        match self.oof {
+            Some(oof) => oof.causes(),
+            None => &[],
+        }
+    }
+
+    /// Type names of tags of the `Oof`.
+    pub fn tags(&self) -> impl Iterator<Item = &'a str> {
+        self.oof.into_iter().flat_map(|oof| oof.tag_names())
+    }
+}
+
+/// Parameter of a generated context, displayed as `$0: &str = "hello world"`.
+#[derive(Clone, Copy)]
+pub struct Parameter<'a>(&'a OofArg);
+
+impl<'a> Parameter<'a> {
+    /// Index of the parameter, as in `$0`.
+    pub fn index(&self) -> usize {
+        self.0.index
+    }
+
+    /// Type of the parameter.
+    pub fn ty(&self) -> &'a str {
+        &self.0.ty
+    }
+
+    /// Debug string of the parameter, if it was captured.
+    pub fn value(&self) -> Option<&'a dyn Display> {
+        self.0.display.as_ref().map(|v| v as &dyn Display)
+    }
+}
+
+impl Display for Parameter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#}", self.0)
+    }
+}
+
+/// Iterator over every error in the chain of an `Oof` as a [Frame](struct.Frame.html), starting from the `Oof`.
+#[derive(Clone)]
+pub struct Frames<'a> {
+    oof: &'a Oof,
+    chain: Enumerate<Chain<'a>>,
+    is_debug: bool,
+    options: &'a ReportOptions,
+}
+
+impl<'a> Frames<'a> {
+    pub(crate) fn new(oof: &'a Oof, options: &'a ReportOptions, is_debug: bool) -> Self {
+        Self {
+            oof,
+            chain: Chain::new(oof).enumerate(),
+            is_debug,
+            options,
+        }
+    }
+
+    /// The `Oof` the frames are of.
+    pub fn oof(&self) -> &'a Oof {
+        self.oof
+    }
+
+    /// Options to render the frames with.
+    pub fn options(&self) -> &'a ReportOptions {
+        self.options
+    }
+
+    fn frame(&self, (depth, error): (usize, &'a (dyn Error + 'static))) -> Frame<'a> {
+        Frame {
+            is_debug: self.is_debug,
+            ..Frame::new(depth, error, self.options)
+        }
+    }
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.chain.next()?;
+        Some(self.frame(next))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chain.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Frames<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.chain.next_back()?;
+        Some(self.frame(next))
+    }
+}
+
+impl ExactSizeIterator for Frames<'_> {}
+
+impl Oof {
+    /// Iterate over every error in the chain as a [Frame](report/struct.Frame.html), starting from this `Oof`.
+    pub fn frames(&self) -> Frames<'_> {
+        Frames::new(self, &options::DISPLAY, false)
+    }
+}
+
+/// Writer that indents every line written to it by four spaces.
+///
+/// If numbered, the first line is prefixed with the number instead, and the following lines are aligned with it.
+///
+/// Ex)
+/// ```rust
+/// use oofs::report::Indented;
+/// use std::fmt::Write;
+///
+/// let mut s = String::new();
+/// write!(Indented::numbered(&mut s, 0), "first\nsecond").unwrap();
+///
+/// assert_eq!(s, "    0: first\n       second");
+/// ```
+pub struct Indented<'a, D: ?Sized> {
+    inner: &'a mut D,
+    number: Option<usize>,
+    started: bool,
+}
+
+impl<'a, D: ?Sized + Write> Indented<'a, D> {
+    /// Indent every line by four spaces.
+    pub fn new(inner: &'a mut D) -> Self {
+        Self {
+            inner,
+            number: None,
+            started: false,
+        }
+    }
+
+    /// Prefix the first line with the number, and align the following lines with it.
+    pub fn numbered(inner: &'a mut D, number: usize) -> Self {
+        Self {
+            inner,
+            number: Some(number),
+            started: false,
+        }
+    }
+}
+
+impl<T> Write for Indented<'_, T>
+where
+    T: ?Sized + Write,
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if !self.started {
+                self.started = true;
+                match self.number {
+                    Some(number) => write!(self.inner, "{: >5}: ", number)?,
+                    None => self.inner.write_str("    ")?,
+                }
+            } else if i > 0 {
+                self.inner.write_char('\n')?;
+                if self.number.is_some() {
+                    self.inner.write_str("       ")?;
+                } else {
+                    self.inner.write_str("    ")?;
+                }
+            }
+
+            self.inner.write_str(line)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+use super::{options::debug, ReportOptions};
+use crate::{tags::TagTable, Oof};
+use std::process::{ExitCode, Termination};
+
+/// Table of exit codes for errors tagged as given types, used by [ExitReport].
+///
+/// An error exits with the code of the first tag given that it, or any `Oof` nested in it, is tagged with.
+/// Errors matching no tag exit with the default code, `1` unless changed with [ExitCodes::with_default].
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oof, ExitCodes};
+///
+/// struct UsageError;
+/// struct NotFound;
+///
+/// let codes = ExitCodes::new().tag::<UsageError>(2).tag::<NotFound>(3);
+///
+/// assert_eq!(codes.code_of(&oof!("missing file").tag::<NotFound>()), 3);
+/// assert_eq!(codes.code_of(&oof!("unknown failure")), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExitCodes {
+    codes: TagTable<u8>,
+    default: u8,
+}
+
+impl Default for ExitCodes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExitCodes {
+    /// Table without any tag, where every error exits with `1`.
+    pub fn new() -> Self {
+        Self {
+            codes: TagTable::new(),
+            default: 1,
+        }
+    }
+
+    /// Exit with `code` for errors tagged as `T`; see [Oof::tagged_nested](../struct.Oof.html#method.tagged_nested).
+    ///
+    /// `0` means success, so it is raised to `1`.
+    pub fn tag<T: 'static>(mut self, code: u8) -> Self {
+        self.codes.push::<T>(code.max(1));
+        self
+    }
+
+    /// Exit with `code` for errors matching no tag.
+    ///
+    /// `0` means success, so it is raised to `1`.
+    pub fn with_default(mut self, code: u8) -> Self {
+        self.default = code.max(1);
+        self
+    }
+
+    /// Exit code of the error.
+    pub fn code_of(&self, error: &Oof) -> u8 {
+        self.codes.get(error).copied().unwrap_or(self.default)
+    }
+}
+
+/// Return type of `main` that prints the report of the error to stderr, and exits with the code given by its tags.
+///
+/// Returning `Result<(), Oof>` from `main` also prints the report, but always exits with `1`.
+/// Like it, the report is prefixed with `Error: `, and rendered with the options of the `Debug` report
+/// unless others are given with [ExitReport::with_options].
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oofs, ExitCodes, ExitReport, Oof};
+///
+/// struct UsageError;
+/// struct NotFound;
+///
+/// #[oofs]
+/// fn run() -> Result<(), Oof> {
+///     // ...
+///     # Ok(())
+/// }
+///
+/// fn main() -> ExitReport {
+///     ExitReport::new(run()).with_codes(ExitCodes::new().tag::<UsageError>(2).tag::<NotFound>(3))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ExitReport {
+    result: Result<(), Oof>,
+    codes: ExitCodes,
+    options: ReportOptions,
+}
+
+impl From<Result<(), Oof>> for ExitReport {
+    fn from(result: Result<(), Oof>) -> Self {
+        Self::new(result)
+    }
+}
+
+impl From<Oof> for ExitReport {
+    fn from(error: Oof) -> Self {
+        Self::new(Err(error))
+    }
+}
+
+impl ExitReport {
+    pub fn new(result: Result<(), Oof>) -> Self {
+        Self {
+            result,
+            codes: ExitCodes::new(),
+            options: *debug(),
+        }
+    }
+
+    /// Exit with codes of the given table.
+    pub fn with_codes(mut self, codes: ExitCodes) -> Self {
+        self.codes = codes;
+        self
+    }
+
+    /// Render the report with the given options instead of the ones of the `Debug` report.
+    pub fn with_options(mut self, options: ReportOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// `0` if there is no error, else the code of the error in the table.
+    pub fn exit_code(&self) -> u8 {
+        match &self.result {
+            Ok(()) => 0,
+            Err(e) => self.codes.code_of(e),
+        }
+    }
+}
+
+impl Termination for ExitReport {
+    fn report(self) -> ExitCode {
+        if let Err(e) = &self.result {
+            eprintln!("Error: {}", e.report(&self.options));
+        }
+
+        ExitCode::from(self.exit_code())
+    }
+}
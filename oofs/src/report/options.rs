@@ -0,0 +1,340 @@
+use super::{handler, Frames};
+use crate::{theme::Theme, Oof};
+use alloc::{borrow::Cow, string::String};
+use core::fmt::{self, Display};
+
+/// Options for rendering the report of `Oof`, passed to [Oof::report](../struct.Oof.html#method.report).
+///
+/// `ReportOptions::new()` renders the same report as `Debug`; use the builder methods to change it.
+///
+/// With the `std` feature, the `Debug` report of `Oof` uses the options given by the environment variable `OOFS_DETAIL`;
+/// see [ReportOptions::from_env]. Without it, the `Debug` report uses `ReportOptions::new()`.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oofs, report::ReportOptions, Oof};
+///
+/// #[oofs]
+/// fn outer() -> Result<(), Oof> {
+///     inner()?;
+///     Ok(())
+/// }
+///
+/// #[oofs]
+/// fn inner() -> Result<u64, Oof> {
+///     Ok("hello world".parse::<u64>()?)
+/// }
+///
+/// let err = outer().unwrap_err();
+///
+/// let options = ReportOptions::new().with_compact(true).with_max_depth(2);
+/// let report = err.report(&options).to_string();
+///
+/// assert!(report.starts_with("inner() failed at `"));
+/// assert!(report.ends_with(": ... 1 more frame"));
+/// assert!(!report.contains('\n'));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ReportOptions {
+    compact: bool,
+    max_depth: Option<usize>,
+    short_types: bool,
+    parameters: bool,
+    attachments: bool,
+    root_debug: bool,
+    collapse_repeats: bool,
+    merge_parameters: bool,
+    theme: Option<Theme>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReportOptions {
+    /// Options of the full report, same as the `Debug` report by default.
+    pub const fn new() -> Self {
+        Self {
+            compact: false,
+            max_depth: None,
+            short_types: false,
+            parameters: true,
+            attachments: true,
+            root_debug: false,
+            collapse_repeats: false,
+            merge_parameters: false,
+            theme: None,
+        }
+    }
+
+    /// Alias of [ReportOptions::new].
+    pub const fn full() -> Self {
+        Self::new()
+    }
+
+    /// Options of a single line report with short type names, for log lines.
+    pub const fn short() -> Self {
+        Self::new().with_compact(true).with_short_types(true)
+    }
+
+    /// Options given by the environment variable `OOFS_DETAIL`.
+    ///
+    /// `OOFS_DETAIL=short` gives [ReportOptions::short], and `OOFS_DETAIL=full` or any other value gives [ReportOptions::full].
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Self {
+        match std::env::var("OOFS_DETAIL") {
+            Ok(detail) if detail.eq_ignore_ascii_case("short") => Self::short(),
+            _ => Self::full(),
+        }
+    }
+
+    /// Render every frame in a single line, separated by `: `.
+    ///
+    /// Only the message and the location of each frame are rendered.
+    pub const fn with_compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Render at most `depth` frames including the outermost one; the rest are counted as `... N more frames`.
+    ///
+    /// The outermost frame is always rendered.
+    pub const fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Shorten type names of parameters and tags, like `String` instead of `alloc::string::String`.
+    pub const fn with_short_types(mut self, short: bool) -> Self {
+        self.short_types = short;
+        self
+    }
+
+    /// Render parameters of generated contexts.
+    pub const fn with_parameters(mut self, show: bool) -> Self {
+        self.parameters = show;
+        self
+    }
+
+    /// Render attachments.
+    pub const fn with_attachments(mut self, show: bool) -> Self {
+        self.attachments = show;
+        self
+    }
+
+    /// Render the root source with `Debug` instead of `Display`, if it is not an `Oof`.
+    pub const fn with_root_debug(mut self, debug: bool) -> Self {
+        self.root_debug = debug;
+        self
+    }
+
+    /// Collapse consecutive frames that differ only by values of parameters into `[frame repeated N times]`.
+    ///
+    /// This keeps recursive calls from flooding the report; see [Frame::is_repeat_of](struct.Frame.html#method.is_repeat_of).
+    pub const fn with_collapse_repeats(mut self, collapse: bool) -> Self {
+        self.collapse_repeats = collapse;
+        self
+    }
+
+    /// Omit parameters of a frame if all their values are shown as parameters of the next frame.
+    ///
+    /// This merges the outer frame into the inner one, when an argument is just passed down; see [Frame::parameters_shown_in](struct.Frame.html#method.parameters_shown_in).
+    pub const fn with_merge_parameters(mut self, merge: bool) -> Self {
+        self.merge_parameters = merge;
+        self
+    }
+
+    /// Render in the given theme instead of the detected one.
+    #[cfg(feature = "color")]
+    pub const fn with_theme(self, theme: Theme) -> Self {
+        self.themed(theme)
+    }
+
+    pub(crate) const fn themed(mut self, theme: Theme) -> Self {
+        self.theme = Some(theme);
+        self
+    }
+
+    /// Whether every frame is rendered in a single line.
+    pub fn compact(&self) -> bool {
+        self.compact
+    }
+
+    /// Maximum number of frames to render, if limited.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Whether type names are shortened.
+    pub fn short_types(&self) -> bool {
+        self.short_types
+    }
+
+    /// Whether parameters are rendered.
+    pub fn parameters(&self) -> bool {
+        self.parameters
+    }
+
+    /// Whether attachments are rendered.
+    pub fn attachments(&self) -> bool {
+        self.attachments
+    }
+
+    /// Whether the root source is rendered with `Debug`.
+    pub fn root_debug(&self) -> bool {
+        self.root_debug
+    }
+
+    /// Whether repeated frames are collapsed.
+    pub fn collapse_repeats(&self) -> bool {
+        self.collapse_repeats
+    }
+
+    /// Whether parameters shown in the next frame are omitted.
+    pub fn merge_parameters(&self) -> bool {
+        self.merge_parameters
+    }
+
+    pub(crate) fn theme(&self) -> &Theme {
+        match &self.theme {
+            Some(theme) => theme,
+            #[cfg(feature = "color")]
+            None => crate::theme::auto(),
+            #[cfg(not(feature = "color"))]
+            None => &DISPLAY_THEME,
+        }
+    }
+
+    /// Type name shortened if [ReportOptions::short_types] is set.
+    pub fn type_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        if self.short_types {
+            short_type_name(name)
+        } else {
+            name.into()
+        }
+    }
+}
+
+/// Options of `Display`, which is never colored.
+pub(crate) static DISPLAY: ReportOptions = ReportOptions::new().themed(Theme::plain());
+
+#[cfg(not(feature = "color"))]
+static DISPLAY_THEME: Theme = Theme::plain();
+
+/// Options of the `Debug` report, read from the environment once per process.
+#[cfg(feature = "std")]
+pub(crate) fn debug() -> &'static ReportOptions {
+    static DEBUG: crate::sync::OnceLock<ReportOptions> = crate::sync::OnceLock::new();
+
+    DEBUG.get_or_init(ReportOptions::from_env)
+}
+
+/// Options of the `Debug` report, without the environment to read them from.
+#[cfg(not(feature = "std"))]
+pub(crate) fn debug() -> &'static ReportOptions {
+    static DEBUG: ReportOptions = ReportOptions::new();
+
+    &DEBUG
+}
+
+/// Shorten a type name by removing the module path of each type in it.
+///
+/// Ex)
+/// ```rust
+/// use oofs::report::short_type_name;
+///
+/// assert_eq!(
+///     short_type_name("&std::collections::HashMap<alloc::string::String, u8>"),
+///     "&HashMap<String, u8>"
+/// );
+/// ```
+pub fn short_type_name(name: &str) -> Cow<'_, str> {
+    if !name.contains("::") {
+        return name.into();
+    }
+
+    let mut short = String::with_capacity(name.len());
+    let mut rest = name;
+
+    while let Some(start) = rest.find(is_path_char) {
+        short.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = rest.find(|c| !is_path_char(c)).unwrap_or(rest.len());
+        let path = &rest[..end];
+
+        // paths starting with `::` follow a qualified path like `<T as Trait>::Assoc`, so they are kept.
+        match path.rfind("::") {
+            Some(i) if !path.starts_with("::") => short.push_str(&path[i + 2..]),
+            _ => short.push_str(path),
+        }
+
+        rest = &rest[end..];
+    }
+
+    short.push_str(rest);
+    short.into()
+}
+
+fn is_path_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == ':'
+}
+
+/// Renders the report of `Oof` with the given options; returned by [Oof::report](../struct.Oof.html#method.report).
+///
+/// `Display` and `Debug` both render the whole report.
+pub struct ReportWith<'a> {
+    oof: &'a Oof,
+    options: &'a ReportOptions,
+}
+
+impl Display for ReportWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        handler().debug(Frames::new(self.oof, self.options, true), f)
+    }
+}
+
+impl fmt::Debug for ReportWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Oof {
+    /// Render the report of this `Oof` with the given options.
+    ///
+    /// See [ReportOptions](report/struct.ReportOptions.html).
+    pub fn report<'a>(&'a self, options: &'a ReportOptions) -> ReportWith<'a> {
+        ReportWith { oof: self, options }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::short_type_name;
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name("u8"), "u8");
+        assert_eq!(short_type_name("alloc::string::String"), "String");
+        assert_eq!(
+            short_type_name("core::option::Option<&alloc::vec::Vec<u8>>"),
+            "Option<&Vec<u8>>"
+        );
+        assert_eq!(
+            short_type_name("(alloc::string::String, [std::path::PathBuf; 2])"),
+            "(String, [PathBuf; 2])"
+        );
+        assert_eq!(
+            short_type_name("alloc::boxed::Box<dyn core::error::Error + core::marker::Send>"),
+            "Box<dyn Error + Send>"
+        );
+        assert_eq!(short_type_name("basic::main::{{closure}}"), "{{closure}}");
+        assert_eq!(
+            short_type_name("<my::Type as my::Trait>::Assoc"),
+            "<Type as Trait>::Assoc"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{error::Error, sync::OnceLock, tags::TagTable, Oof};
+use alloc::{borrow::Cow, boxed::Box, string::String};
+use core::fmt;
+
+static MAPPING: OnceLock<Box<dyn HttpMapping>> = OnceLock::new();
+
+/// Maps errors to HTTP status codes and public bodies, at the boundary of an API.
+///
+/// [HttpStatuses] maps tags in precedence order, and is enough for most services;
+/// implement this trait to map errors in other ways, like by their [code](struct.Oof.html#method.code).
+///
+/// The mapping set with [set_http_mapping] is used by [Oof::public_error](struct.Oof.html#method.public_error)
+/// and, with the `axum` feature, by `IntoResponse` for `Oof`.
+pub trait HttpMapping: 'static + Send + Sync {
+    /// Status and public body of the error.
+    ///
+    /// The body is sent to clients; it must not include anything from the report, like parameters and attachments.
+    fn public_error(&self, error: &Oof) -> PublicError;
+
+    /// Log the error before it is turned into a response.
+    ///
+    /// By default, the `Debug` report is logged with `tracing::error!` with the `tracing` feature;
+    /// without it, nothing is logged.
+    /// The report includes parameters and attachments, so override this to use your logger, or to leave out sensitive values.
+    fn log(&self, error: &Oof, public: &PublicError) {
+        #[cfg(feature = "tracing")]
+        tracing::error!(status = public.status, "{error:?}");
+        #[cfg(not(feature = "tracing"))]
+        let _ = (error, public);
+    }
+}
+
+/// Status and body of an error, safe to send to clients of an API.
+///
+/// With the `axum` feature, it is serialized as the JSON body of the response,
+/// like `{"status":404,"message":"user not found","code":"E-USER-404"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "axum", derive(serde::Serialize))]
+pub struct PublicError {
+    /// HTTP status code.
+    pub status: u16,
+    /// Message for clients.
+    pub message: Cow<'static, str>,
+    /// Error code of the `Oof`, if any; see [Oof::code](struct.Oof.html#method.code).
+    #[cfg_attr(feature = "axum", serde(skip_serializing_if = "Option::is_none"))]
+    pub code: Option<String>,
+}
+
+/// Table of HTTP statuses and public messages for errors tagged as given types.
+///
+/// Give more specific tags first: the first tag the error is tagged with, checked like
+/// [Oof::tagged_nested](struct.Oof.html#method.tagged_nested), decides the response.
+/// Errors matching no tag are `500 internal server error`, unless changed with [HttpStatuses::with_default].
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oof, HttpMapping, HttpStatuses};
+///
+/// struct InvalidInput;
+/// struct NotFound;
+///
+/// let statuses = HttpStatuses::new()
+///     .tag::<InvalidInput>(400, "invalid input")
+///     .tag::<NotFound>(404, "not found");
+///
+/// let err = oof!("user {} not found", 42).tag::<NotFound>().attach("secret");
+/// let public = statuses.public_error(&err);
+///
+/// assert_eq!(public.status, 404);
+/// assert_eq!(public.message, "not found");
+///
+/// assert_eq!(statuses.public_error(&oof!("db is down")).status, 500);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpStatuses {
+    statuses: TagTable<(u16, Cow<'static, str>)>,
+    default: (u16, Cow<'static, str>),
+}
+
+impl Default for HttpStatuses {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HttpStatuses {
+    /// Table without any tag, where every error is `500 internal server error`.
+    pub const fn new() -> Self {
+        Self {
+            statuses: TagTable::new(),
+            default: (500, Cow::Borrowed("internal server error")),
+        }
+    }
+
+    /// Respond with `status` and `message` for errors tagged as `T`;
+    /// see [Oof::tagged_nested](struct.Oof.html#method.tagged_nested).
+    pub fn tag<T: 'static>(mut self, status: u16, message: impl Into<Cow<'static, str>>) -> Self {
+        self.statuses.push::<T>((status, message.into()));
+        self
+    }
+
+    /// Respond with `status` and `message` for errors matching no tag.
+    pub fn with_default(mut self, status: u16, message: impl Into<Cow<'static, str>>) -> Self {
+        self.default = (status, message.into());
+        self
+    }
+}
+
+impl HttpMapping for HttpStatuses {
+    fn public_error(&self, error: &Oof) -> PublicError {
+        let (status, message) = self.statuses.get(error).unwrap_or(&self.default);
+
+        PublicError {
+            status: *status,
+            message: message.clone(),
+            code: error.code().map(String::from),
+        }
+    }
+}
+
+/// Set the mapping used by [Oof::public_error](struct.Oof.html#method.public_error) and `IntoResponse` in this process.
+///
+/// Until it is set, every error is `500 internal server error`.
+/// The mapping can be set only once; later calls return an error and keep the first mapping.
+pub fn set_http_mapping(mapping: impl HttpMapping) -> Result<(), SetHttpMappingError> {
+    MAPPING
+        .set(Box::new(mapping))
+        .map_err(|_| SetHttpMappingError)
+}
+
+pub(crate) fn mapping() -> &'static dyn HttpMapping {
+    static DEFAULT: HttpStatuses = HttpStatuses::new();
+
+    match MAPPING.get() {
+        Some(mapping) => mapping.as_ref(),
+        None => &DEFAULT,
+    }
+}
+
+/// Error returned by [set_http_mapping] if a mapping is already set.
+#[derive(Debug)]
+pub struct SetHttpMappingError;
+
+impl fmt::Display for SetHttpMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "http mapping is already set")
+    }
+}
+
+impl Error for SetHttpMappingError {}
+
+impl Oof {
+    /// Status and public body of this `Oof`, by the mapping set with [set_http_mapping](fn.set_http_mapping.html).
+    pub fn public_error(&self) -> PublicError {
+        mapping().public_error(self)
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use super::{mapping, PublicError};
+    use crate::Oof;
+    use axum_core::response::{IntoResponse, Response};
+    use http::{header, StatusCode};
+
+    impl IntoResponse for PublicError {
+        fn into_response(self) -> Response {
+            let status =
+                StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+            match serde_json::to_vec(&self) {
+                Ok(body) => {
+                    (status, [(header::CONTENT_TYPE, "application/json")], body).into_response()
+                }
+                Err(_) => status.into_response(),
+            }
+        }
+    }
+
+    /// Responds with the public error given by the mapping set with [set_http_mapping](../fn.set_http_mapping.html),
+    /// after logging the full report with [HttpMapping::log](../trait.HttpMapping.html#method.log).
+    impl IntoResponse for Oof {
+        fn into_response(self) -> Response {
+            let mapping = mapping();
+            let public = mapping.public_error(&self);
+
+            mapping.log(&self, &public);
+
+            public.into_response()
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{builder::OofBuilder, Oof};
+use core::{fmt, future::Future};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Policy of [retry] and [retry_async]: how many times to call the function, how long to wait in between,
+/// and which errors are worth another attempt.
+///
+/// Without any [RetryPolicy::retry_on] tag, every error is retried.
+///
+/// Ex)
+/// ```rust
+/// use oofs::RetryPolicy;
+/// use std::time::Duration;
+///
+/// struct RetryTag;
+///
+/// // waits 100ms, 200ms, 400ms, 500ms between 5 attempts, each shortened by up to 10%.
+/// let policy = RetryPolicy::new(5)
+///     .with_delay(Duration::from_millis(100))
+///     .with_backoff(2.0)
+///     .with_max_delay(Duration::from_millis(500))
+///     .with_jitter(0.1)
+///     .retry_on::<RetryTag>();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    attempts: usize,
+    delay: Duration,
+    backoff: f64,
+    max_delay: Option<Duration>,
+    jitter: f64,
+    retry_on: Vec<fn(&Oof) -> bool>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Call the function at most `attempts` times, waiting 100ms before the first retry and twice as long before each next one.
+    pub fn new(attempts: usize) -> Self {
+        Self {
+            attempts: attempts.max(1),
+            delay: Duration::from_millis(100),
+            backoff: 2.0,
+            max_delay: None,
+            jitter: 0.0,
+            retry_on: Vec::new(),
+        }
+    }
+
+    /// Wait before the first retry.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Multiply the wait by `factor` after each retry; `1.0` waits the same each time.
+    pub fn with_backoff(mut self, factor: f64) -> Self {
+        self.backoff = factor.max(0.0);
+        self
+    }
+
+    /// Never wait longer than `max_delay` before a retry.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Shorten each wait by a random fraction of up to `jitter`, between `0.0` and `1.0`,
+    /// so that callers failing together do not retry together.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Retry errors tagged as `T` in any nested `Oof`; see [Oof::tagged_nested](struct.Oof.html#method.tagged_nested).
+    ///
+    /// Once a tag is given, errors without any of the given tags are returned without retrying.
+    pub fn retry_on<T: 'static>(mut self) -> Self {
+        self.retry_on.push(|e| e.tagged_nested::<T>());
+        self
+    }
+
+    fn is_retryable(&self, error: &Oof) -> bool {
+        self.retry_on.is_empty() || self.retry_on.iter().any(|f| f(error))
+    }
+
+    /// Wait before the `retry`th retry, starting from `0`.
+    fn delay(&self, retry: usize) -> Duration {
+        let exp = i32::try_from(retry).unwrap_or(i32::MAX);
+        let secs = self.delay.as_secs_f64() * self.backoff.powi(exp);
+
+        let mut delay = Duration::try_from_secs_f64(secs).unwrap_or(Duration::MAX);
+        if let Some(max_delay) = self.max_delay {
+            delay = delay.min(max_delay);
+        }
+
+        if self.jitter > 0.0 {
+            delay = delay.mul_f64(1.0 - self.jitter * random());
+        }
+
+        delay
+    }
+}
+
+/// Attached to the error of each failed attempt kept by [retry] and [retry_async].
+///
+/// Get them in order with [Oof::attachments_of](struct.Oof.html#method.attachments_of).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Attempt {
+    /// Number of the attempt, starting from `1`.
+    pub number: usize,
+    /// When the attempt returned the error.
+    pub failed_at: SystemTime,
+}
+
+impl fmt::Debug for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "attempt {} failed at ", self.number)?;
+
+        match self.failed_at.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => write_timestamp(f, since_epoch),
+            Err(_) => write!(f, "{:?}", self.failed_at),
+        }
+    }
+}
+
+/// Call `f` until it succeeds, following the policy.
+///
+/// If the first attempt fails with an error that should not be retried, it is returned as is.
+/// Otherwise, the error of every attempt is kept, with an [Attempt] attached, as causes of a single `retried N times` frame;
+/// see [Oof::causes](struct.Oof.html#method.causes).
+///
+/// This function blocks the thread while waiting; use [retry_async] in async code.
+///
+/// Ex)
+/// ```rust
+/// use oofs::*;
+/// use std::time::Duration;
+///
+/// struct RetryTag;
+///
+/// #[oofs]
+/// fn connect(port: u16) -> Result<(), Oof> {
+///     ensure!(port != 0, "port {} is closed", port, { tag: [RetryTag] });
+///     Ok(())
+/// }
+///
+/// let policy = RetryPolicy::new(3)
+///     .with_delay(Duration::from_millis(1))
+///     .retry_on::<RetryTag>();
+///
+/// let err = retry(&policy, || connect(0)).unwrap_err();
+///
+/// assert!(err.to_string().starts_with("retried 2 times"));
+/// assert_eq!(err.causes().len(), 3);
+/// assert_eq!(err.attachments_of::<Attempt>().count(), 3);
+/// ```
+#[cfg_attr(feature = "location", track_caller)]
+pub fn retry<T, F>(policy: &RetryPolicy, mut f: F) -> Result<T, Oof>
+where
+    F: FnMut() -> Result<T, Oof>,
+{
+    let builder = Oof::builder();
+    let mut errors = Vec::new();
+
+    loop {
+        let error = match f() {
+            Ok(t) => return Ok(t),
+            Err(e) => e,
+        };
+
+        match next_delay(policy, &mut errors, error) {
+            Ok(delay) => thread::sleep(delay),
+            Err(errors) => return Err(retried(builder, errors)),
+        }
+    }
+}
+
+/// Async variant of [retry], waiting on the future returned by `sleep` between attempts,
+/// like `tokio::time::sleep`, so that it is not tied to any runtime.
+///
+/// Ex)
+/// ```rust
+/// use oofs::*;
+/// use std::time::Duration;
+///
+/// # struct RetryTag;
+/// # async fn fetch(url: &str) -> Result<String, Oof> { Ok(url.to_owned()) }
+/// # async fn sleep(_: Duration) {}
+/// # async fn _ex() -> Result<(), Oof> {
+/// let policy = RetryPolicy::new(3).retry_on::<RetryTag>();
+///
+/// let body = retry_async(&policy, sleep, || fetch("https://example.com")).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(feature = "location", track_caller)]
+pub fn retry_async<'a, T, F, Fut, S, SFut>(
+    policy: &'a RetryPolicy,
+    mut sleep: S,
+    mut f: F,
+) -> impl Future<Output = Result<T, Oof>> + 'a
+where
+    F: 'a + FnMut() -> Fut,
+    Fut: Future<Output = Result<T, Oof>>,
+    S: 'a + FnMut(Duration) -> SFut,
+    SFut: Future<Output = ()>,
+{
+    // location is taken here, since async blocks cannot track the caller.
+    let builder = Oof::builder();
+
+    async move {
+        let mut errors = Vec::new();
+
+        loop {
+            let error = match f().await {
+                Ok(t) => return Ok(t),
+                Err(e) => e,
+            };
+
+            match next_delay(policy, &mut errors, error) {
+                Ok(delay) => sleep(delay).await,
+                Err(errors) => return Err(retried(builder, errors)),
+            }
+        }
+    }
+}
+
+/// Keep the error of the failed attempt, and return how long to wait before the next one,
+/// or the errors to return if there should be none.
+fn next_delay(
+    policy: &RetryPolicy,
+    errors: &mut Vec<Oof>,
+    error: Oof,
+) -> Result<Duration, Vec<Oof>> {
+    let number = errors.len() + 1;
+    let retryable = policy.is_retryable(&error);
+
+    errors.push(error.attach_value(Attempt {
+        number,
+        failed_at: SystemTime::now(),
+    }));
+
+    if retryable && number < policy.attempts {
+        Ok(policy.delay(number - 1))
+    } else {
+        Err(core::mem::take(errors))
+    }
+}
+
+fn retried(builder: OofBuilder, mut errors: Vec<Oof>) -> Oof {
+    let retries = errors.len() - 1;
+
+    // nothing was retried; return the error as is.
+    if retries == 0 {
+        return errors.remove(0);
+    }
+
+    let noun = if retries == 1 { "time" } else { "times" };
+
+    builder
+        .with_custom(format!("retried {retries} {noun}"))
+        .with_causes(errors)
+        .build()
+}
+
+/// Random number in `[0, 1)`, from the random keys of `RandomState`.
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Write as RFC 3339 in UTC, like `2022-11-20T09:41:07.052Z`.
+fn write_timestamp(f: &mut fmt::Formatter<'_>, since_epoch: Duration) -> fmt::Result {
+    let secs = since_epoch.as_secs();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // civil date from days since epoch; http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let (era, doe) = (z / 146_097, z % 146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    write!(
+        f,
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3_600,
+        rem / 60 % 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
+}
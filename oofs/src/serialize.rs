@@ -0,0 +1,439 @@
+use crate::{
+    attachments::Attachment,
+    chain::Chain,
+    context::{ArgValue, Context, OofArg, OofGeneratedContext, OofIdent, OofMethod, OofReceiver},
+    error::Error,
+    meta::Meta,
+    tags::{Severity, Tags},
+    Inner, Oof,
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "location")]
+use crate::context::Location;
+
+/// Version of the schema `Oof` is serialized into.
+///
+/// This is bumped whenever the shape of the serialized report changes.
+///
+/// Version 2 added the error code and severity of each `Oof` frame, and version 3 added the causes aggregated by
+/// `Oof::multi`; reports of older versions are still accepted by self-describing formats like JSON.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Serializes the whole error chain as a versioned report.
+///
+/// Each error in the chain becomes a frame; nested `Oof`s are serialized with their context,
+/// location, tags and attachments, and foreign errors are serialized with their `Display` message.
+/// Causes aggregated by `Oof::multi` are serialized as lists of frames in the frame of the `Oof` holding them.
+///
+/// Ex)
+/// ```rust
+/// # use oofs::*;
+/// let err = oof!("custom error").attach(123u8);
+///
+/// let json = serde_json::to_value(&err).unwrap();
+///
+/// assert_eq!(json["version"], oofs::SCHEMA_VERSION);
+/// assert_eq!(json["frames"][0]["oof"]["message"], "custom error");
+/// assert_eq!(json["frames"][0]["oof"]["attachments"][0], "123");
+/// ```
+impl Serialize for Oof {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Report::new(self).serialize(serializer)
+    }
+}
+
+/// Rebuilds `Oof` from a serialized report.
+///
+/// The rebuilt `Oof` is displayed the same as the original one.
+/// Tags are resolved into types registered with [register_tag](fn.register_tag.html),
+/// and foreign errors are rebuilt as [RemoteError](struct.RemoteError.html).
+///
+/// The schema does not rely on self-describing formats, so compact binary formats work as well as JSON.
+///
+/// Ex)
+/// ```rust
+/// # use oofs::*;
+/// let err = oof!("custom error").attach(123u8);
+/// let json = serde_json::to_string(&err).unwrap();
+///
+/// let rebuilt: Oof = serde_json::from_str(&json).unwrap();
+///
+/// assert_eq!(rebuilt.to_string(), err.to_string());
+///
+/// // backtraces are not serialized, so the report differs only by them.
+/// #[cfg(not(feature = "backtrace"))]
+/// assert_eq!(format!("{rebuilt:?}"), format!("{err:?}"));
+/// ```
+impl<'de> Deserialize<'de> for Oof {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let report = Report::deserialize(deserializer)?;
+
+        if report.version > SCHEMA_VERSION {
+            return Err(de::Error::custom(format_args!(
+                "unsupported report schema version {}, expected at most {SCHEMA_VERSION}",
+                report.version
+            )));
+        }
+
+        report.into_oof().map_err(de::Error::custom)
+    }
+}
+
+/// Foreign error rebuilt from a deserialized report.
+///
+/// Only the `Display` message of the original error is preserved.
+#[derive(Debug)]
+pub struct RemoteError {
+    message: String,
+    source: Option<Box<dyn 'static + Send + Sync + Error>>,
+}
+
+impl Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.message, f)
+    }
+}
+
+impl Error for RemoteError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.source {
+            Some(e) => Some(e.as_ref()),
+            None => None,
+        }
+    }
+}
+
+impl RemoteError {
+    /// `Display` message of the original error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Report<'a> {
+    version: u32,
+    frames: Vec<Frame<'a>>,
+}
+
+impl<'a> Report<'a> {
+    fn new(oof: &'a Oof) -> Self {
+        Self {
+            version: SCHEMA_VERSION,
+            frames: frames(oof),
+        }
+    }
+
+    fn into_oof(self) -> Result<Oof, &'static str> {
+        rebuild(self.frames, 0)
+    }
+}
+
+fn frames(oof: &Oof) -> Vec<Frame<'_>> {
+    Chain::new(oof)
+        .map(|e| match e.downcast_ref::<Oof>() {
+            Some(oof) => Frame::Oof(Box::new(OofFrame::new(oof))),
+            None => Frame::Foreign {
+                message: e.to_string().into(),
+            },
+        })
+        .collect()
+}
+
+/// Causes nested deeper than this are rejected, so that a hostile report cannot overflow the stack while rebuilding.
+const MAX_DEPTH: usize = 128;
+
+/// `depth` is the number of `Oof::multi` causes the frames are nested in.
+fn rebuild(frames: Vec<Frame<'_>>, depth: usize) -> Result<Oof, &'static str> {
+    if depth > MAX_DEPTH {
+        return Err("report nests causes deeper than 128 levels");
+    }
+
+    let mut source: Option<Box<dyn 'static + Send + Sync + Error>> = None;
+
+    for frame in frames.into_iter().rev() {
+        source = Some(match frame {
+            Frame::Oof(f) => Box::new(f.into_oof(source.take(), depth)?),
+            Frame::Foreign { message } => Box::new(RemoteError {
+                message: message.into_owned(),
+                source: source.take(),
+            }),
+        });
+    }
+
+    source
+        .and_then(|source| source.downcast::<Oof>().ok())
+        .map(|oof| *oof)
+        .ok_or("report must start with an `Oof` frame")
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Frame<'a> {
+    Oof(Box<OofFrame<'a>>),
+    Foreign { message: Cow<'a, str> },
+}
+
+#[derive(Serialize, Deserialize)]
+struct OofFrame<'a> {
+    message: Cow<'a, str>,
+    context: ContextRepr<'a>,
+    location: Option<LocationRepr<'a>>,
+    tags: Vec<Cow<'a, str>>,
+    attachments: Vec<Cow<'a, str>>,
+    #[serde(default)]
+    code: Option<Cow<'a, str>>,
+    #[serde(default)]
+    severity: Option<Severity>,
+    #[serde(default)]
+    causes: Vec<Vec<Frame<'a>>>,
+}
+
+impl<'a> OofFrame<'a> {
+    fn new(oof: &'a Oof) -> Self {
+        #[cfg(feature = "location")]
+        let location = oof.inner.location.as_ref().map(|l| LocationRepr {
+            file: l.file().into(),
+            line: l.line,
+            column: l.column,
+        });
+        #[cfg(not(feature = "location"))]
+        let location = None;
+
+        Self {
+            message: format!("{:#}", oof.inner.context).into(),
+            context: ContextRepr::new(&oof.inner.context),
+            location,
+            tags: oof
+                .inner
+                .tags
+                .stable_names()
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            attachments: oof
+                .inner
+                .attachments
+                .iter()
+                .map(|a| match a {
+                    Attachment::Formatted(s) => s.as_str().into(),
+                    Attachment::Lazy(l) => l.get().into(),
+                    Attachment::Value(_) => a.to_string().into(),
+                })
+                .collect(),
+            code: oof.own_code().map(Into::into),
+            severity: oof.inner.meta.as_ref().and_then(|m| m.severity),
+            causes: oof.causes().iter().map(frames).collect(),
+        }
+    }
+
+    /// Fails if any of the causes does not start with an `Oof` frame, or they are nested too deep.
+    fn into_oof(
+        self,
+        source: Option<Box<dyn 'static + Send + Sync + Error>>,
+        depth: usize,
+    ) -> Result<Oof, &'static str> {
+        let causes = self
+            .causes
+            .into_iter()
+            .map(|frames| rebuild(frames, depth + 1))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Oof::new(Inner {
+            source,
+            context: self.context.into_context(),
+            tags: Tags::from_names(self.tags.into_iter().map(Cow::into_owned)),
+            attachments: self
+                .attachments
+                .into_iter()
+                .map(|a| Attachment::Formatted(a.into_owned()))
+                .collect(),
+            causes,
+            meta: Meta {
+                code: self.code.map(|c| c.into_owned().into()),
+                severity: self.severity,
+                #[cfg(feature = "backtrace")]
+                backtrace: None,
+                #[cfg(feature = "tracing")]
+                span_trace: None,
+            }
+            .boxed(),
+            #[cfg(feature = "location")]
+            location: self
+                .location
+                .map(|l| Location::with_file(l.file.into_owned(), l.line, l.column)),
+        }))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ContextRepr<'a> {
+    Generated(GeneratedRepr<'a>),
+    Custom(Cow<'a, str>),
+    None,
+}
+
+impl<'a> ContextRepr<'a> {
+    fn new(context: &'a Context) -> Self {
+        match context {
+            Context::Generated(c) => Self::Generated(GeneratedRepr::new(c)),
+            Context::Custom(m) => Self::Custom(m.as_str().into()),
+            Context::None => Self::None,
+        }
+    }
+
+    fn into_context(self) -> Context {
+        match self {
+            Self::Generated(c) => Context::Generated(c.into_context()),
+            Self::Custom(m) => Context::Custom(m.into_owned()),
+            Self::None => Context::None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct GeneratedRepr<'a> {
+    receiver: ReceiverRepr<'a>,
+    chain: Vec<MethodRepr<'a>>,
+    returns_option: bool,
+}
+
+impl<'a> GeneratedRepr<'a> {
+    fn new(c: &'a OofGeneratedContext) -> Self {
+        Self {
+            receiver: ReceiverRepr::new(&c.receiver),
+            chain: c.chain.iter().map(MethodRepr::new).collect(),
+            returns_option: c.returns_option,
+        }
+    }
+
+    fn into_context(self) -> OofGeneratedContext {
+        OofGeneratedContext {
+            receiver: self.receiver.into_receiver(),
+            chain: self
+                .chain
+                .into_iter()
+                .map(MethodRepr::into_method)
+                .collect(),
+            returns_option: self.returns_option,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverRepr<'a> {
+    Ident(IdentRepr<'a>),
+    Method(MethodRepr<'a>),
+    Arg(ArgRepr<'a>),
+}
+
+impl<'a> ReceiverRepr<'a> {
+    fn new(receiver: &'a OofReceiver) -> Self {
+        match receiver {
+            OofReceiver::Ident(i) => Self::Ident(IdentRepr::new(i)),
+            OofReceiver::Method(m) => Self::Method(MethodRepr::new(m)),
+            OofReceiver::Arg(a) => Self::Arg(ArgRepr::new(a)),
+        }
+    }
+
+    fn into_receiver(self) -> OofReceiver {
+        match self {
+            Self::Ident(i) => OofReceiver::Ident(i.into_ident()),
+            Self::Method(m) => OofReceiver::Method(m.into_method()),
+            Self::Arg(a) => OofReceiver::Arg(a.into_arg()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct IdentRepr<'a> {
+    name: Cow<'a, str>,
+    is_async: bool,
+}
+
+impl<'a> IdentRepr<'a> {
+    fn new(i: &'a OofIdent) -> Self {
+        Self {
+            name: i.name.as_ref().into(),
+            is_async: i.is_async,
+        }
+    }
+
+    fn into_ident(self) -> OofIdent {
+        OofIdent {
+            name: self.name.into_owned().into(),
+            is_async: self.is_async,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MethodRepr<'a> {
+    name: Cow<'a, str>,
+    is_async: bool,
+    args: Vec<ArgRepr<'a>>,
+}
+
+impl<'a> MethodRepr<'a> {
+    fn new(m: &'a OofMethod) -> Self {
+        Self {
+            name: m.name.as_ref().into(),
+            is_async: m.is_async,
+            args: m.args.iter().map(ArgRepr::new).collect(),
+        }
+    }
+
+    fn into_method(self) -> OofMethod {
+        OofMethod {
+            name: self.name.into_owned().into(),
+            is_async: self.is_async,
+            args: self.args.into_iter().map(ArgRepr::into_arg).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArgRepr<'a> {
+    index: usize,
+    ty: Cow<'a, str>,
+    value: Option<Cow<'a, str>>,
+}
+
+impl<'a> ArgRepr<'a> {
+    fn new(a: &'a OofArg) -> Self {
+        Self {
+            index: a.index,
+            ty: a.ty.as_ref().into(),
+            value: a.display.as_ref().map(|d| match d {
+                ArgValue::Formatted(s) => s.as_str().into(),
+                ArgValue::Snapshot(_) => d.to_string().into(),
+            }),
+        }
+    }
+
+    fn into_arg(self) -> OofArg {
+        OofArg {
+            index: self.index,
+            ty: self.ty.into_owned().into(),
+            display: self.value.map(|v| ArgValue::Formatted(v.into_owned())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LocationRepr<'a> {
+    file: Cow<'a, str>,
+    line: u32,
+    column: u32,
+}
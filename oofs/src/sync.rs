@@ -0,0 +1,98 @@
+//! Locks of `std`, or spin locks of `spin` without the `std` feature.
+//!
+//! Poisoned locks are used as is, since every value behind them is valid after a panic.
+
+use core::ops::DerefMut;
+
+#[cfg(feature = "serde")]
+use core::ops::Deref;
+
+#[cfg(feature = "std")]
+use std::sync::PoisonError;
+
+pub(crate) struct OnceLock<T>(
+    #[cfg(feature = "std")] std::sync::OnceLock<T>,
+    #[cfg(not(feature = "std"))] spin::Once<T>,
+);
+
+impl<T> OnceLock<T> {
+    pub(crate) const fn new() -> Self {
+        #[cfg(feature = "std")]
+        return Self(std::sync::OnceLock::new());
+        #[cfg(not(feature = "std"))]
+        return Self(spin::Once::new());
+    }
+
+    pub(crate) fn get(&self) -> Option<&T> {
+        self.0.get()
+    }
+
+    pub(crate) fn get_or_init(&self, f: impl FnOnce() -> T) -> &T {
+        #[cfg(feature = "std")]
+        return self.0.get_or_init(f);
+        #[cfg(not(feature = "std"))]
+        return self.0.call_once(f);
+    }
+
+    /// Set the value if not set yet, else return the given value back.
+    pub(crate) fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.get_or_init(|| value.take().expect("initialized only once"));
+
+        match value {
+            Some(value) => Err(value),
+            None => Ok(()),
+        }
+    }
+}
+
+pub(crate) struct Mutex<T>(
+    #[cfg(feature = "std")] std::sync::Mutex<T>,
+    #[cfg(not(feature = "std"))] spin::Mutex<T>,
+);
+
+impl<T> Mutex<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        #[cfg(feature = "std")]
+        return Self(std::sync::Mutex::new(value));
+        #[cfg(not(feature = "std"))]
+        return Self(spin::Mutex::new(value));
+    }
+
+    pub(crate) fn lock(&self) -> impl DerefMut<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        return self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        return self.0.lock();
+    }
+}
+
+#[cfg(feature = "serde")]
+pub(crate) struct RwLock<T>(
+    #[cfg(feature = "std")] std::sync::RwLock<T>,
+    #[cfg(not(feature = "std"))] spin::RwLock<T>,
+);
+
+#[cfg(feature = "serde")]
+impl<T> RwLock<T> {
+    pub(crate) const fn new(value: T) -> Self {
+        #[cfg(feature = "std")]
+        return Self(std::sync::RwLock::new(value));
+        #[cfg(not(feature = "std"))]
+        return Self(spin::RwLock::new(value));
+    }
+
+    pub(crate) fn read(&self) -> impl Deref<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        return self.0.read().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        return self.0.read();
+    }
+
+    pub(crate) fn write(&self) -> impl DerefMut<Target = T> + '_ {
+        #[cfg(feature = "std")]
+        return self.0.write().unwrap_or_else(PoisonError::into_inner);
+        #[cfg(not(feature = "std"))]
+        return self.0.write();
+    }
+}
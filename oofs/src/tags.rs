@@ -1,30 +1,453 @@
-use std::{any::TypeId, collections::HashSet};
+use crate::sync::OnceLock;
+use alloc::{borrow::Cow, collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{
+    any::{type_name, Any, TypeId},
+    fmt,
+};
+use smallvec::SmallVec;
 
-#[derive(Debug, Clone)]
+#[cfg(any(feature = "std", feature = "http"))]
+use crate::Oof;
+
+/// Tag with parent tags and metadata.
+///
+/// Implementing this trait is optional; any `'static` type can be used as a tag.
+/// Use `#[derive(Tag)]` to implement it, so that the tag is also registered for `tagged::<P>()` to match it
+/// for each of its ancestors `P`; the derive needs the `tag_registry` feature, enabled by default.
+///
+/// Ex)
+/// ```rust
+/// # #[cfg(feature = "tag_registry")]
+/// # fn main() {
+/// use oofs::{oof, Tag};
+///
+/// #[derive(Tag)]
+/// struct Retryable;
+///
+/// #[derive(Tag)]
+/// #[tag(parent(Retryable))]
+/// struct Transient;
+///
+/// #[derive(Tag)]
+/// #[tag(parent(Transient), name = "connection reset", severity = Warning)]
+/// struct ConnectionReset;
+///
+/// let err = oof!("custom error").tag::<ConnectionReset>();
+///
+/// assert!(err.tagged::<Transient>());
+/// assert!(err.tagged::<Retryable>());
+/// assert_eq!(err.tag_infos().next().unwrap().name(), "connection reset");
+/// # }
+/// # #[cfg(not(feature = "tag_registry"))]
+/// # fn main() {}
+/// ```
+pub trait Tag: 'static {
+    /// Display name of the tag; the derive uses the type's name by default.
+    const NAME: &'static str;
+    /// Description of the tag.
+    const DESCRIPTION: Option<&'static str> = None;
+    /// Severity of errors tagged with this tag.
+    const SEVERITY: Option<Severity> = None;
+    /// Direct parents of the tag.
+    const PARENTS: &'static [fn() -> TypeId] = &[];
+}
+
+/// Severity of an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+            Self::Critical => "critical",
+        })
+    }
+}
+
+/// Metadata of a type implementing [Tag], registered by `#[derive(Tag)]` with the `tag_registry` feature.
+#[derive(Debug)]
+pub struct TagInfo {
+    id: fn() -> TypeId,
+    name: &'static str,
+    description: Option<&'static str>,
+    severity: Option<Severity>,
+    parents: &'static [fn() -> TypeId],
+}
+
+#[cfg(feature = "tag_registry")]
+inventory::collect!(TagInfo);
+
+impl TagInfo {
+    pub const fn of<T: Tag>() -> Self {
+        Self {
+            id: TypeId::of::<T>,
+            name: T::NAME,
+            description: T::DESCRIPTION,
+            severity: T::SEVERITY,
+            parents: T::PARENTS,
+        }
+    }
+
+    /// Registered metadata of the tag with the given type id.
+    pub fn get(id: TypeId) -> Option<&'static TagInfo> {
+        hierarchy().get(&id).map(|node| node.info)
+    }
+
+    pub fn type_id(&self) -> TypeId {
+        (self.id)()
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn description(&self) -> Option<&'static str> {
+        self.description
+    }
+
+    pub fn severity(&self) -> Option<Severity> {
+        self.severity
+    }
+
+    /// Type ids of direct parents.
+    pub fn parents(&self) -> impl Iterator<Item = TypeId> {
+        self.parents.iter().map(|p| p())
+    }
+}
+
+/// Registered tag, with all of its ancestors resolved once.
+struct Node {
+    info: &'static TagInfo,
+    // sorted, for `is_a` to binary search.
+    ancestors: Vec<TypeId>,
+}
+
+fn hierarchy() -> &'static BTreeMap<TypeId, Node> {
+    static HIERARCHY: OnceLock<BTreeMap<TypeId, Node>> = OnceLock::new();
+
+    HIERARCHY.get_or_init(|| {
+        let infos: BTreeMap<TypeId, &'static TagInfo> =
+            registered().map(|info| (info.type_id(), info)).collect();
+
+        infos
+            .iter()
+            .map(|(id, info)| {
+                let node = Node {
+                    info,
+                    ancestors: ancestors(&infos, info),
+                };
+
+                (*id, node)
+            })
+            .collect()
+    })
+}
+
+/// Tags built into this crate, and tags registered by `#[derive(Tag)]`.
+fn registered() -> impl Iterator<Item = &'static TagInfo> {
+    static BUILTIN: [TagInfo; 1] = [TagInfo::of::<crate::Panic>()];
+
+    let builtin = BUILTIN.iter();
+
+    #[cfg(feature = "tag_registry")]
+    let builtin = builtin.chain(inventory::iter::<TagInfo>);
+
+    builtin
+}
+
+/// All ancestors of the tag, sorted.
+fn ancestors(infos: &BTreeMap<TypeId, &'static TagInfo>, info: &TagInfo) -> Vec<TypeId> {
+    let mut ancestors = Vec::new();
+    let mut stack: Vec<TypeId> = info.parents().collect();
+
+    while let Some(id) = stack.pop() {
+        // guard against cycles in the declared parents.
+        if ancestors.contains(&id) {
+            continue;
+        }
+        ancestors.push(id);
+
+        if let Some(info) = infos.get(&id) {
+            stack.extend(info.parents());
+        }
+    }
+
+    ancestors.sort_unstable();
+    ancestors
+}
+
+/// Check if `id` is `ancestor` or one of its descendants.
+fn is_a(id: TypeId, ancestor: TypeId) -> bool {
+    id == ancestor
+        || hierarchy()
+            .get(&id)
+            .is_some_and(|node| node.ancestors.binary_search(&ancestor).is_ok())
+}
+
+#[derive(Clone)]
 pub struct Tags {
-    set: HashSet<TypeId>,
+    // Errors are rarely tagged with more than a few types, so a linear scan over an inline vector
+    // is cheaper than hashing, and usually needs no allocation of its own.
+    tags: SmallVec<[TagEntry; 2]>,
+}
+
+#[derive(Clone)]
+struct TagEntry {
+    // `None` for tags of a deserialized `Oof` that could not be resolved to a registered type.
+    id: Option<TypeId>,
+    name: Cow<'static, str>,
+    // `Arc` keeps `Tags` cloneable.
+    value: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for TagEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TagEntry")
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl fmt::Debug for Tags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Tags {
     pub fn new() -> Self {
         Tags {
-            set: HashSet::new(),
+            tags: SmallVec::new(),
         }
     }
 
     pub fn tag<T: 'static>(&mut self) {
-        self.set.insert(TypeId::of::<T>());
+        if !self.contains(TypeId::of::<T>()) {
+            self.tags.push(TagEntry {
+                id: Some(TypeId::of::<T>()),
+                name: type_name::<T>().into(),
+                value: None,
+            });
+        }
+    }
+
+    /// Tag with the type of the value, and keep the value to be retrieved with [Tags::value].
+    ///
+    /// If already tagged with the same type, the value is replaced.
+    pub fn tag_with<T: 'static + Send + Sync>(&mut self, value: T) {
+        let id = TypeId::of::<T>();
+        let value = Some(Arc::new(value) as Arc<dyn Any + Send + Sync>);
+
+        match self.tags.iter_mut().find(|t| t.id == Some(id)) {
+            Some(tag) => tag.value = value,
+            None => self.tags.push(TagEntry {
+                id: Some(id),
+                name: type_name::<T>().into(),
+                value,
+            }),
+        }
+    }
+
+    /// Value of the tag of type `T`, if tagged with [Tags::tag_with].
+    pub fn value<T: 'static>(&self) -> Option<&T> {
+        let id = TypeId::of::<T>();
+
+        self.tags
+            .iter()
+            .find(|t| t.id == Some(id))
+            .and_then(|t| t.value.as_deref())
+            .and_then(|v| v.downcast_ref())
     }
 
     pub fn untag<T: 'static>(&mut self) {
-        self.set.remove(&TypeId::of::<T>());
+        let id = TypeId::of::<T>();
+        self.tags.retain(|t| t.id != Some(id));
     }
 
+    /// Check if tagged with `T`, or with one of its descendants declared with [Tag].
     pub fn tagged<T: 'static>(&self) -> bool {
-        self.set.contains(&TypeId::of::<T>())
+        let ancestor = TypeId::of::<T>();
+        self.iter().any(|id| is_a(*id, ancestor))
+    }
+
+    fn contains(&self, id: TypeId) -> bool {
+        self.tags.iter().any(|t| t.id == Some(id))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &TypeId> {
-        self.set.iter()
+        self.tags.iter().filter_map(|t| t.id.as_ref())
+    }
+
+    /// Iterate over metadata of tagged types that implement [Tag].
+    pub fn infos(&self) -> impl Iterator<Item = &'static TagInfo> + '_ {
+        self.iter().filter_map(|id| TagInfo::get(*id))
+    }
+
+    /// Whether both are tagged with the same types in the same order, and with the same values, if any.
+    ///
+    /// Values cannot be compared, so values of different tags are never the same, even if they are equal.
+    pub(crate) fn same_as(&self, other: &Tags) -> bool {
+        self.tags.len() == other.tags.len()
+            && self.tags.iter().zip(&other.tags).all(|(a, b)| {
+                let same_value = match (&a.value, &b.value) {
+                    (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                    (a, b) => a.is_none() && b.is_none(),
+                };
+
+                a.id == b.id && a.name == b.name && same_value
+            })
+    }
+
+    /// Iterate over type names of tagged types.
+    ///
+    /// Tags of a deserialized `Oof` that could not be resolved to a registered type are listed by their
+    /// serialized name.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(|t| t.name.as_ref())
+    }
+}
+
+/// Checks whether the error is tagged as a type of the table.
+#[cfg(any(feature = "std", feature = "http"))]
+type IsTagged = fn(&Oof) -> bool;
+
+/// Values for errors tagged as given types, like exit codes or HTTP statuses.
+///
+/// Tags are checked in all nested `Oof`s, in the order they are pushed; the first match wins.
+#[cfg(any(feature = "std", feature = "http"))]
+#[derive(Debug, Clone)]
+pub(crate) struct TagTable<V> {
+    entries: Vec<(IsTagged, V)>,
+}
+
+#[cfg(any(feature = "std", feature = "http"))]
+impl<V> TagTable<V> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push<T: 'static>(&mut self, value: V) {
+        self.entries.push((|e| e.tagged_nested::<T>(), value));
+    }
+
+    /// Value of the first tag the error is tagged with, if any.
+    pub(crate) fn get(&self, error: &Oof) -> Option<&V> {
+        self.entries
+            .iter()
+            .find(|(tagged, _)| tagged(error))
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use registry::register_tag;
+
+#[cfg(feature = "serde")]
+mod registry {
+    use super::{TagEntry, Tags};
+    use crate::sync::{OnceLock, RwLock};
+    use alloc::{collections::BTreeMap, string::String, vec::Vec};
+    use core::any::{type_name, TypeId};
+
+    #[derive(Default)]
+    struct Registry {
+        by_name: BTreeMap<&'static str, (TypeId, &'static str)>,
+        by_type: BTreeMap<TypeId, &'static str>,
+    }
+
+    fn registry() -> &'static RwLock<Registry> {
+        static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+        REGISTRY.get_or_init(|| RwLock::new(Registry::default()))
+    }
+
+    /// Register a stable name for the tag type `T`.
+    ///
+    /// Serialized `Oof`s list the tag by the registered name instead of `std::any::type_name::<T>()`,
+    /// and deserialized `Oof`s resolve either name back into `T`, so `tagged::<T>()` and `tagged_nested::<T>()`
+    /// keep working across process boundaries.
+    ///
+    /// Both the sending and the receiving side should register the same name.
+    ///
+    /// Ex)
+    /// ```rust
+    /// # use oofs::*;
+    /// struct RetryTag;
+    ///
+    /// oofs::register_tag::<RetryTag>("retry");
+    ///
+    /// let json = serde_json::to_string(&oof!("custom error").tag::<RetryTag>()).unwrap();
+    /// let err: Oof = serde_json::from_str(&json).unwrap();
+    ///
+    /// assert!(err.tagged::<RetryTag>());
+    /// ```
+    pub fn register_tag<T: 'static>(name: &'static str) {
+        let id = TypeId::of::<T>();
+        let ty = type_name::<T>();
+
+        let mut registry = registry().write();
+
+        registry.by_name.insert(name, (id, ty));
+        registry.by_name.insert(ty, (id, ty));
+        registry.by_type.insert(id, name);
+    }
+
+    impl Tags {
+        /// Names of tagged types, preferring the registered name over the type name.
+        pub(crate) fn stable_names(&self) -> Vec<&str> {
+            let registry = registry().read();
+
+            self.tags
+                .iter()
+                .map(|t| match t.id.and_then(|id| registry.by_type.get(&id)) {
+                    Some(name) => name,
+                    None => t.name.as_ref(),
+                })
+                .collect()
+        }
+
+        /// Resolve serialized tag names into registered types.
+        pub(crate) fn from_names(names: impl IntoIterator<Item = String>) -> Self {
+            let registry = registry().read();
+
+            let tags = names
+                .into_iter()
+                .map(|name| match registry.by_name.get(name.as_str()) {
+                    Some((id, ty)) => TagEntry {
+                        id: Some(*id),
+                        name: (*ty).into(),
+                        value: None,
+                    },
+                    None => TagEntry {
+                        id: None,
+                        name: name.into(),
+                        value: None,
+                    },
+                })
+                .collect();
+
+            Tags { tags }
+        }
     }
 }
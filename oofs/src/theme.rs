@@ -0,0 +1,180 @@
+use core::fmt::{self, Display};
+
+#[cfg(feature = "color")]
+use crate::{
+    report::{self, ReportOptions},
+    Oof,
+};
+
+/// ANSI style applied to a part of the report, given as SGR parameters like `"1;36"`.
+///
+/// An empty style writes the part as is, without any escape codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style(&'static str);
+
+impl Style {
+    /// Style without any escape codes.
+    pub const PLAIN: Style = Style("");
+
+    /// Style with the given SGR parameters, like `"1;36"` for bold cyan.
+    #[cfg(feature = "color")]
+    pub const fn new(sgr: &'static str) -> Self {
+        Self(sgr)
+    }
+
+    /// SGR parameters of this style.
+    #[cfg(feature = "color")]
+    pub fn sgr(&self) -> &'static str {
+        self.0
+    }
+
+    /// Wrap the value to be displayed in this style.
+    pub fn paint<D: Display>(self, value: D) -> Painted<D> {
+        Painted(self, value)
+    }
+}
+
+/// Value displayed in a [Style].
+pub struct Painted<D>(Style, D);
+
+impl<D: Display> Display for Painted<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0 .0.is_empty() {
+            return Display::fmt(&self.1, f);
+        }
+
+        write!(f, "\x1b[{}m", self.0 .0)?;
+        Display::fmt(&self.1, f)?;
+        write!(f, "\x1b[0m")
+    }
+}
+
+/// Styles of each part of the report.
+///
+/// Start from [Theme::colored] or [Theme::plain] and replace the styles to change;
+/// see [Oof::display_with](struct.Oof.html#method.display_with).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Call expression of generated contexts, like `$0.parse()`.
+    pub call: Style,
+    /// Type names of parameters.
+    pub ty: Style,
+    /// Values of parameters.
+    pub value: Style,
+    /// Locations where errors were built.
+    pub location: Style,
+    /// Tag names.
+    pub tag: Style,
+    /// Section headings, like `Parameters:` and `Caused by:`.
+    pub heading: Style,
+}
+
+impl Theme {
+    /// Theme without any escape codes; output is the same as without the `color` feature.
+    pub const fn plain() -> Self {
+        Self {
+            call: Style::PLAIN,
+            ty: Style::PLAIN,
+            value: Style::PLAIN,
+            location: Style::PLAIN,
+            tag: Style::PLAIN,
+            heading: Style::PLAIN,
+        }
+    }
+
+    /// Default colored theme.
+    #[cfg(feature = "color")]
+    pub const fn colored() -> Self {
+        Self {
+            call: Style::new("1;36"),
+            ty: Style::new("2"),
+            value: Style::new("33"),
+            location: Style::new("35"),
+            tag: Style::new("1;30;46"),
+            heading: Style::new("1"),
+        }
+    }
+
+    /// [Theme::colored] if stderr is a terminal and `NO_COLOR` is not set, else [Theme::plain].
+    ///
+    /// This is the theme of the `Debug` report; it is detected once per process.
+    /// Without the `std` feature, there is no terminal to detect, so this is always [Theme::plain].
+    #[cfg(feature = "color")]
+    pub fn auto() -> Self {
+        *auto()
+    }
+}
+
+#[cfg(all(feature = "color", feature = "std"))]
+pub(crate) fn auto() -> &'static Theme {
+    use crate::sync::OnceLock;
+    use std::io::IsTerminal;
+
+    static AUTO: OnceLock<Theme> = OnceLock::new();
+
+    AUTO.get_or_init(|| {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+        if !no_color && std::io::stderr().is_terminal() {
+            Theme::colored()
+        } else {
+            Theme::plain()
+        }
+    })
+}
+
+#[cfg(all(feature = "color", not(feature = "std")))]
+pub(crate) fn auto() -> &'static Theme {
+    static AUTO: Theme = Theme::plain();
+
+    &AUTO
+}
+
+/// Displays `Oof` in the given theme; returned by [Oof::display_with](struct.Oof.html#method.display_with).
+///
+/// `Display` renders the error itself, and `Debug` renders the whole report with the default [ReportOptions],
+/// regardless of `OOFS_DETAIL`.
+#[cfg(feature = "color")]
+pub struct DisplayWith<'a> {
+    oof: &'a Oof,
+    options: ReportOptions,
+}
+
+#[cfg(feature = "color")]
+impl Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        report::handler().display(&report::Frame::new(0, self.oof, &self.options), f)
+    }
+}
+
+#[cfg(feature = "color")]
+impl fmt::Debug for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        report::handler().debug(report::Frames::new(self.oof, &self.options, true), f)
+    }
+}
+
+#[cfg(feature = "color")]
+impl Oof {
+    /// Display this `Oof` in the given theme, overriding the detected one.
+    ///
+    /// Ex)
+    /// ```rust
+    /// use oofs::{oof, Style, Theme};
+    ///
+    /// let mut theme = Theme::colored();
+    /// theme.tag = Style::new("1;35");
+    ///
+    /// let err = oof!("custom error");
+    ///
+    /// eprintln!("{:?}", err.display_with(theme));
+    /// assert_eq!(err.display_with(Theme::plain()).to_string(), err.to_string());
+    /// ```
+    pub fn display_with(&self, theme: Theme) -> DisplayWith<'_> {
+        DisplayWith {
+            oof: self,
+            options: report::DISPLAY.with_theme(theme),
+        }
+    }
+}
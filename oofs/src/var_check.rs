@@ -1,5 +1,8 @@
-use core::fmt;
-use std::marker::PhantomData;
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{fmt, marker::PhantomData};
 
 pub trait __VarCheck {
     type Target;
@@ -28,6 +31,15 @@ pub trait __VarCheck {
                 .flatten(),
         )
     }
+
+    #[inline]
+    fn try_lazy_debug<F, S>(&self, should_exec: bool, f: F) -> __InstantExecute
+    where
+        F: FnOnce(&Self) -> Option<S>,
+        S: ToString,
+    {
+        self.try_lazy(should_exec, f)
+    }
 }
 
 impl<T> __VarCheck for __VarWrapper<T> {
@@ -72,6 +84,74 @@ impl<T: Copy> __VarWrapper<T> {
     {
         __LazyExecute(*self, should_exec.then_some(f), PhantomData)
     }
+
+    #[inline]
+    pub fn try_lazy_debug<F, S>(&self, should_exec: bool, f: F) -> __LazyExecute<Self, F, S>
+    where
+        F: FnOnce(Self) -> Option<S>,
+        S: ToString,
+    {
+        self.try_lazy(should_exec, f)
+    }
+}
+
+/// Stores primitive values as is, so that they are only formatted when the error is displayed.
+///
+/// This takes `self` by value, so it is picked before `try_lazy_debug` of `__VarWrapper<T: Copy>` and `__VarCheck`.
+/// Other values may be borrowed, and cannot outlive the call, so they are still formatted when the error is built.
+///
+/// This is only implemented for the listed types, not for any `T: Copy + 'static`;
+/// impls are picked regardless of lifetimes, so such an impl would also be picked for `&'a str`, and fail to compile.
+pub trait __VarSnapshot: Sized {
+    fn try_lazy_debug<F, S>(self, should_exec: bool, f: F) -> __SnapshotExecute
+    where
+        F: FnOnce(Self) -> Option<S>,
+        S: ToString;
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct __SnapshotExecute(Option<__Snapshot>);
+impl __SnapshotExecute {
+    #[inline]
+    pub fn exec(self) -> Option<__Snapshot> {
+        self.0
+    }
+}
+
+macro_rules! snapshots {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        #[derive(Clone, Copy)]
+        pub enum __Snapshot {
+            $($variant($ty)),*
+        }
+
+        impl fmt::Debug for __Snapshot {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $(Self::$variant(v) => fmt::Debug::fmt(v, f)),*
+                }
+            }
+        }
+
+        $(
+            impl __VarSnapshot for __VarWrapper<$ty> {
+                #[inline]
+                fn try_lazy_debug<F, S>(self, should_exec: bool, _f: F) -> __SnapshotExecute
+                where
+                    F: FnOnce(Self) -> Option<S>,
+                    S: ToString,
+                {
+                    __SnapshotExecute(should_exec.then_some(__Snapshot::$variant(self.0)))
+                }
+            }
+        )*
+    };
+}
+
+snapshots! {
+    U8(u8), U16(u16), U32(u32), U64(u64), U128(u128), Usize(usize),
+    I8(i8), I16(i16), I32(i32), I64(i64), I128(i128), Isize(isize),
+    F32(f32), F64(f64), Bool(bool), Char(char),
 }
 
 #[derive(Debug, Clone)]
@@ -93,15 +173,17 @@ where
     #[inline]
     pub fn exec(self) -> Option<String> {
         let Self(arg, f, _) = self;
-        f.map(|f| f(arg).map(|s| s.to_string())).flatten()
+        f.and_then(|f| f(arg).map(|s| s.to_string()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::{AtomicBool, Ordering};
+    use alloc::borrow::ToOwned;
+    use core::sync::atomic::{AtomicBool, Ordering};
 
+    #[allow(dead_code)]
     pub struct NoDebug(String);
 
     #[test]
@@ -169,8 +251,37 @@ mod tests {
         }
 
         generic_debug(5u64);
+        #[allow(clippy::needless_borrows_for_generic_args)]
         generic_debug(&5u64);
         generic_no_debug(5u64);
         generic_no_debug(&5u64);
     }
+
+    #[test]
+    fn test_snapshot_primitives() {
+        let fn_called = AtomicBool::from(false);
+
+        let w = __VarWrapper(5u64);
+        let w_val = w
+            .try_lazy_debug(true, |val| {
+                fn_called.store(true, Ordering::Relaxed);
+
+                val.try_debug_fmt()
+            })
+            .exec();
+
+        // primitive values are stored as is, and the fn is never called.
+        assert!(!fn_called.load(Ordering::Relaxed));
+        assert_eq!(format!("{:?}", w_val.unwrap()), "5");
+
+        // skipped values are not stored.
+        let w_val = w.try_lazy_debug(false, |val| val.try_debug_fmt()).exec();
+        assert!(w_val.is_none());
+
+        // references fall back to formatting.
+        let x = 5u64;
+        let r = __VarWrapper(&x);
+        let r_val = r.try_lazy_debug(true, |val| val.try_debug_fmt()).exec();
+        assert_eq!(r_val.as_deref(), Some("5"));
+    }
 }
@@ -0,0 +1,113 @@
+use oofs::{oofs, Oof, OofExt};
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+struct RequestId(u64);
+
+#[oofs]
+fn handle(id: u64) -> Result<(), Oof> {
+    fetch("hello world")._attach_value(RequestId(id))?;
+
+    Ok(())
+}
+
+#[oofs]
+fn fetch(text: &str) -> Result<u64, Oof> {
+    let ret = text
+        .parse::<u64>()
+        ._attach_value(Duration::from_millis(300))
+        ._attach("borrowed")?;
+
+    Ok(ret)
+}
+
+#[test]
+fn retrieves_attachments_through_nested_oofs() {
+    let err = handle(7).unwrap_err();
+
+    assert_eq!(err.attachment::<RequestId>(), Some(&RequestId(7)));
+    assert_eq!(
+        err.attachment::<Duration>(),
+        Some(&Duration::from_millis(300))
+    );
+    assert_eq!(err.attachment::<&str>(), None);
+    assert_eq!(err.attachment::<u64>(), None);
+}
+
+#[test]
+fn lists_attachments_of_type_in_order() {
+    let err = oofs::oof!("custom error")
+        .attach_value(RequestId(1))
+        .attach(RequestId(2))
+        .attach_value(RequestId(3));
+
+    let ids: Vec<_> = err.attachments_of::<RequestId>().collect();
+
+    assert_eq!(ids, [&RequestId(1), &RequestId(3)]);
+}
+
+#[test]
+fn displays_typed_attachments() {
+    let err = handle(7).unwrap_err();
+
+    let report = format!("{err:?}");
+
+    assert!(report.contains("0: RequestId(7)"));
+    assert!(report.contains("0: 300ms"));
+    assert!(report.contains("1: \"borrowed\""));
+}
+
+#[test]
+fn ensure_attaches_values() {
+    fn check(len: usize) -> Result<(), Oof> {
+        oofs::ensure!(len < 5, {
+            attach_value: [RequestId(len as u64)]
+        });
+
+        Ok(())
+    }
+
+    let err = check(10).unwrap_err();
+
+    assert_eq!(err.attachment::<RequestId>(), Some(&RequestId(10)));
+}
+
+#[test]
+fn defers_attachments_until_displayed() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let err = {
+        let calls = calls.clone();
+        oofs::oof!("custom error").attach_deferred(move || {
+            calls.fetch_add(1, Ordering::Relaxed);
+            "deferred attachment"
+        })
+    };
+
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+    let report = format!("{err:?}");
+    assert!(report.contains("0: deferred attachment"));
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // the result is cached after the first render.
+    assert_eq!(format!("{err:?}"), report);
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn calls_lazy_attachments_with_borrowed_values_right_away() {
+    let text = String::from("borrowed");
+
+    let err = oofs::oof!("custom error").attach_lazy(|| text.len());
+
+    // the closure is already called, so `text` is not borrowed anymore.
+    drop(text);
+
+    assert!(format!("{err:?}").contains("0: 8"));
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "axum")]
+
+use axum_core::response::IntoResponse;
+use http::{header, StatusCode};
+use http_body_util::BodyExt;
+use oofs::*;
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+struct NotFound;
+
+/// Keeps the logged reports instead of printing them.
+struct Logged {
+    statuses: HttpStatuses,
+    logs: Arc<Mutex<Vec<String>>>,
+}
+
+impl HttpMapping for Logged {
+    fn public_error(&self, error: &Oof) -> PublicError {
+        self.statuses.public_error(error)
+    }
+
+    fn log(&self, error: &Oof, _public: &PublicError) {
+        self.logs.lock().unwrap().push(format!("{error:?}"));
+    }
+}
+
+#[oofs]
+fn get_user(id: u64, token: &str) -> Result<(), Oof> {
+    lookup(id, token)?;
+
+    Ok(())
+}
+
+#[oofs]
+fn lookup(id: u64, _token: &str) -> Result<(), Oof> {
+    ensure!(id == 1, "no user {}", id, { tag: [NotFound] });
+
+    Ok(())
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(Noop));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
+
+#[test]
+fn responds_with_public_error() {
+    let logs = Arc::new(Mutex::new(Vec::new()));
+
+    set_http_mapping(Logged {
+        statuses: HttpStatuses::new().tag::<NotFound>(404, "user not found"),
+        logs: logs.clone(),
+    })
+    .unwrap();
+
+    let res = get_user(7, "secret-token")
+        .unwrap_err()
+        .with_code("E-USER-404")
+        .into_response();
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/json");
+
+    let body = block_on(res.into_body().collect()).unwrap().to_bytes();
+    assert_eq!(
+        body,
+        r#"{"status":404,"message":"user not found","code":"E-USER-404"}"#
+    );
+
+    let res = oof!("db is down").into_response();
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    // the full report goes to the logs.
+    let logs = logs.lock().unwrap();
+    assert_eq!(logs.len(), 2);
+    assert!(logs[0].contains("no user 7"));
+    assert!(logs[0].contains("\"secret-token\""));
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "backtrace")]
+
+use oofs::{oofs, Oof};
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner("hello world")?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner(text: &str) -> Result<u64, Oof> {
+    let ret = text.parse::<u64>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn captures_innermost_backtrace() {
+    std::env::set_var("RUST_LIB_BACKTRACE", "1");
+
+    let err = outer().unwrap_err();
+
+    let backtrace = err.backtrace().expect("backtrace should be captured");
+    assert!(backtrace.to_string().contains("inner"));
+
+    let debug = format!("{err:?}");
+    assert_eq!(debug.matches("Stack backtrace:").count(), 1);
+
+    let caused_by = debug.find("Caused by:").unwrap();
+    assert!(debug.find("Stack backtrace:").unwrap() > caused_by);
+}
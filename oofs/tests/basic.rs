@@ -48,6 +48,7 @@ struct MyStruct {
 // Context will be injected to all methods that return a `Result`.
 #[oofs]
 impl MyStruct {
+    #[allow(dead_code)]
     fn new() -> Result<Self, Oof> {
         let field = "hi".to_owned();
 
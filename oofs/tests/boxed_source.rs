@@ -0,0 +1,65 @@
+use oofs::{oofs, Oof, OofExt};
+use std::{error::Error, num::ParseIntError};
+
+type BoxError = Box<dyn Error + Send + Sync>;
+
+struct RetryTag;
+
+fn parse(text: &str) -> Result<u64, BoxError> {
+    Ok(text.parse::<u64>()?)
+}
+
+fn nested() -> Result<u64, BoxError> {
+    Ok(inner("hello world")?)
+}
+
+#[oofs]
+fn inner(text: &str) -> Result<u64, Oof> {
+    let ret = parse(text)._tag::<RetryTag>()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn outer() -> Result<u64, Oof> {
+    let ret = nested()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn keeps_boxed_error_as_source() {
+    let err = inner("hello world").unwrap_err();
+
+    assert!(err.tagged::<RetryTag>());
+
+    // the boxed error is the source itself, not a box wrapped in another box.
+    let source = err.source().unwrap();
+    assert!(source.is::<ParseIntError>());
+    assert!(err.root_cause().is::<ParseIntError>());
+    assert!(err.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[test]
+fn sees_through_boxed_oofs() {
+    let err = outer().unwrap_err();
+
+    assert!(err.tagged_nested::<RetryTag>());
+    assert!(err.find_source::<Oof>().is_some());
+    assert!(err.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[oofs]
+fn with_context() -> Result<u64, Oof> {
+    let ret = parse("hello world")._context("parsing failed")?;
+
+    Ok(ret)
+}
+
+#[test]
+fn builds_context_on_boxed_error() {
+    let err = with_context().unwrap_err();
+
+    assert!(err.to_string().starts_with("parsing failed"));
+    assert!(err.source().unwrap().is::<ParseIntError>());
+}
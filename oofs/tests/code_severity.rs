@@ -0,0 +1,131 @@
+use oofs::{ensure, ensure_eq, oof, oofs, Oof, OofExt, Severity};
+
+#[cfg(feature = "tag_registry")]
+#[derive(oofs::Tag)]
+#[tag(severity = Warning)]
+struct Transient;
+
+#[oofs(code = "E-STORAGE-000")]
+fn outer() -> Result<(), Oof> {
+    inner()?;
+
+    Ok(())
+}
+
+#[oofs(code("E-STORAGE-042"), severity = Critical)]
+fn inner() -> Result<u64, Oof> {
+    Ok("hello world".parse::<u64>()?)
+}
+
+#[oofs]
+fn without_meta() -> Result<u64, Oof> {
+    Ok("hello world".parse::<u64>()?)
+}
+
+#[test]
+fn resolves_outermost_and_innermost() {
+    let err = outer().unwrap_err();
+
+    assert_eq!(err.code(), Some("E-STORAGE-000"));
+    assert_eq!(err.code_rev(), Some("E-STORAGE-042"));
+    assert_eq!(err.severity(), Some(Severity::Critical));
+    assert_eq!(err.severity_rev(), Some(Severity::Critical));
+}
+
+#[oofs]
+fn with_meta_methods() -> Result<u64, Oof> {
+    let ret = "hello world"
+        .parse::<u64>()
+        ._code(String::from("E-PARSE-001"))
+        ._severity(Severity::Info)?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn none_with_code() -> Result<u8, Oof> {
+    Ok(None::<u8>._code("E-NONE")?)
+}
+
+#[test]
+fn sets_with_meta_methods() {
+    let err = with_meta_methods().unwrap_err();
+
+    assert_eq!(err.code(), Some("E-PARSE-001"));
+    assert_eq!(err.severity(), Some(Severity::Info));
+
+    let err = none_with_code().unwrap_err();
+
+    assert_eq!(err.code(), Some("E-NONE"));
+    assert_eq!(err.severity(), None);
+}
+
+#[cfg(feature = "tag_registry")]
+#[test]
+fn falls_back_to_tag_severity() {
+    let err = oof!("custom error").tag::<Transient>();
+
+    assert_eq!(err.severity(), Some(Severity::Warning));
+
+    let err = err.with_severity(Severity::Info);
+
+    assert_eq!(err.severity(), Some(Severity::Info));
+}
+
+#[test]
+fn prints_report_header() {
+    let err = outer().unwrap_err();
+    let debug = format!("{err:?}");
+
+    assert!(debug.starts_with(
+        "critical[E-STORAGE-000]: inner() failed at `oofs/tests/code_severity.rs:10:"
+    ));
+    // the header is printed once, for the whole chain.
+    assert_eq!(debug.matches("E-STORAGE").count(), 1);
+
+    let err = oof!("custom error").with_code("E-001");
+    assert!(format!("{err:?}").starts_with("[E-001]: custom error at `"));
+
+    let err = oof!("custom error").with_severity(Severity::Error);
+    assert!(format!("{err:?}").starts_with("error: custom error at `"));
+
+    // `Display` is not changed.
+    assert!(err.to_string().starts_with("custom error at `"));
+}
+
+#[test]
+fn no_header_without_meta() {
+    let err = without_meta().unwrap_err();
+
+    assert_eq!(err.code(), None);
+    assert_eq!(err.severity(), None);
+    assert!(format!("{err:?}").starts_with("$0.parse() failed at `"));
+}
+
+#[oofs]
+fn ensured(x: u8) -> Result<(), Oof> {
+    ensure!(x > 1, "x is too small", {
+        code: "E-VALIDATION-001",
+        severity: Warning
+    });
+
+    ensure_eq!(x, 2, {
+        code: "E-VALIDATION-002",
+        severity: Severity::Error
+    });
+
+    Ok(())
+}
+
+#[test]
+fn sets_in_ensure() {
+    let err = ensured(1).unwrap_err();
+
+    assert_eq!(err.code(), Some("E-VALIDATION-001"));
+    assert_eq!(err.severity(), Some(Severity::Warning));
+
+    let err = ensured(3).unwrap_err();
+
+    assert_eq!(err.code(), Some("E-VALIDATION-002"));
+    assert_eq!(err.severity(), Some(Severity::Error));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "color")]
+
+use oofs::{oofs, report::ReportOptions, Oof, OofExt, Style, Theme};
+
+struct RetryTag;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner(123)?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner(x: u32) -> Result<u64, Oof> {
+    let ret = "hello world".parse::<u64>()._tag::<RetryTag>()._attach(x)?;
+
+    Ok(ret)
+}
+
+#[test]
+fn plain_theme_matches_default_output() {
+    let err = outer().unwrap_err();
+
+    let plain = Theme::plain();
+
+    assert_eq!(err.display_with(plain).to_string(), err.to_string());
+    assert!(!format!("{:?}", err.display_with(plain)).contains('\x1b'));
+}
+
+#[test]
+fn renders_default_report_options() {
+    let err = outer().unwrap_err();
+
+    let options = ReportOptions::new().with_theme(Theme::plain());
+
+    assert_eq!(
+        format!("{:?}", err.display_with(Theme::plain())),
+        err.report(&options).to_string()
+    );
+}
+
+#[test]
+fn colors_parts_of_report() {
+    let err = outer().unwrap_err();
+
+    let report = format!("{:?}", err.display_with(Theme::colored()));
+
+    assert!(
+        report.starts_with("\x1b[1;36minner($0)\x1b[0m failed at `\x1b[35moofs/tests/color.rs:")
+    );
+    assert!(report.contains("$0: \x1b[2mu32\x1b[0m = \x1b[33m123\x1b[0m\n"));
+    assert!(report.contains("\x1b[1mCaused by:\x1b[0m"));
+    assert!(report.contains("\x1b[1;30;46mcolor::RetryTag\x1b[0m\n"));
+}
+
+#[test]
+fn overrides_styles_of_theme() {
+    let err = oofs::oof!("custom error").tag::<RetryTag>();
+
+    let mut theme = Theme::plain();
+    theme.tag = Style::new("7");
+
+    let report = format!("{:?}", err.display_with(theme));
+
+    assert!(report.contains("    \x1b[7mcolor::RetryTag\x1b[0m\n"));
+    assert!(report.starts_with("custom error at `oofs/tests/color.rs:"));
+}
+
+#[test]
+fn paints_values() {
+    assert_eq!(Style::new("1").paint(12).to_string(), "\x1b[1m12\x1b[0m");
+    assert_eq!(Style::PLAIN.paint(12).to_string(), "12");
+}
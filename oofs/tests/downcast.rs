@@ -0,0 +1,102 @@
+use oofs::{oofs, Oof};
+use std::{error::Error, io, num::ParseIntError};
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    middle()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn middle() -> Result<(), Oof> {
+    inner()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner() -> Result<(), Oof> {
+    std::fs::read("does/not/exist")?;
+
+    Ok(())
+}
+
+#[test]
+fn finds_root_cause() {
+    let err = outer().unwrap_err();
+
+    let root = err.root_cause().downcast_ref::<io::Error>().unwrap();
+
+    assert_eq!(root.kind(), io::ErrorKind::NotFound);
+
+    let err = oofs::oof!("custom error");
+
+    assert!(err.root_cause().is::<Oof>());
+}
+
+#[test]
+fn downcasts_through_nested_oofs() {
+    let mut err = outer().unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::NotFound
+    );
+    assert!(err.downcast_ref::<ParseIntError>().is_none());
+    assert!(std::ptr::eq(err.downcast_ref::<Oof>().unwrap(), &err));
+
+    *err.downcast_mut::<io::Error>().unwrap() = io::Error::other("replaced");
+
+    assert_eq!(err.root_cause().to_string(), "replaced");
+}
+
+#[test]
+fn downcasts_through_causes() {
+    let mut err = Oof::multi([oofs::oof!("custom error"), outer().unwrap_err()]);
+
+    assert!(err.downcast_ref::<io::Error>().is_some());
+
+    *err.downcast_mut::<io::Error>().unwrap() = io::Error::other("replaced");
+
+    assert_eq!(
+        err.downcast_ref::<io::Error>().unwrap().to_string(),
+        "replaced"
+    );
+}
+
+#[test]
+fn finds_sources() {
+    let err = outer().unwrap_err();
+
+    let nested = err.find_source::<Oof>().unwrap();
+    assert!(nested.to_string().starts_with("inner() failed"));
+
+    assert!(err.find_source::<io::Error>().is_some());
+    assert!(nested.source().is_some());
+}
+
+#[test]
+fn takes_ownership_of_source() {
+    let err = outer().unwrap_err();
+    let report = format!("{err:?}");
+
+    let err = err.downcast::<ParseIntError>().unwrap_err();
+    assert_eq!(format!("{err:?}"), report);
+
+    let io_err = err.downcast::<io::Error>().unwrap();
+    assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn takes_ownership_of_cause() {
+    let err = Oof::multi([oofs::oof!("custom error"), outer().unwrap_err()]);
+    let report = format!("{err:?}");
+
+    let err = err.downcast::<ParseIntError>().unwrap_err();
+    assert_eq!(err.causes().len(), 2);
+    assert_eq!(format!("{err:?}"), report);
+
+    let io_err = err.downcast::<io::Error>().unwrap();
+    assert_eq!(io_err.kind(), io::ErrorKind::NotFound);
+}
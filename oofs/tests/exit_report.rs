@@ -0,0 +1,76 @@
+#![cfg(feature = "std")]
+
+use oofs::{report::ReportOptions, *};
+use std::process::{ExitCode, Termination};
+
+struct UsageError;
+struct NotFound;
+
+#[oofs]
+fn open(path: &str) -> Result<(), Oof> {
+    ensure!(!path.is_empty(), "path is empty", { tag: [UsageError] });
+    ensure!(path == "config.toml", "{} not found", path, { tag: [NotFound] });
+
+    Ok(())
+}
+
+#[oofs]
+fn run(path: &str) -> Result<(), Oof> {
+    open(path)?;
+
+    Ok(())
+}
+
+fn codes() -> ExitCodes {
+    ExitCodes::new().tag::<UsageError>(2).tag::<NotFound>(3)
+}
+
+#[test]
+fn maps_tags_to_exit_codes() {
+    let exit = |path| ExitReport::new(run(path)).with_codes(codes()).exit_code();
+
+    assert_eq!(exit("config.toml"), 0);
+    assert_eq!(exit(""), 2);
+    assert_eq!(exit("missing.toml"), 3);
+
+    let untagged = ExitReport::from(oof!("unknown failure")).with_codes(codes());
+    assert_eq!(untagged.exit_code(), 1);
+
+    let untagged = ExitReport::from(oof!("unknown failure")).with_codes(codes().with_default(70));
+    assert_eq!(untagged.exit_code(), 70);
+}
+
+#[test]
+fn never_exits_with_zero_for_errors() {
+    let codes = ExitCodes::new().tag::<NotFound>(0).with_default(0);
+
+    assert_eq!(codes.code_of(&oof!("missing file").tag::<NotFound>()), 1);
+    assert_eq!(codes.code_of(&oof!("unknown failure")), 1);
+
+    let exit = ExitReport::new(run("missing.toml")).with_codes(codes);
+    assert_eq!(exit.exit_code(), 1);
+}
+
+#[test]
+fn first_matching_tag_wins() {
+    let err = oof!("both").tag::<NotFound>().tag::<UsageError>();
+
+    assert_eq!(codes().code_of(&err), 2);
+    assert_eq!(
+        ExitCodes::new()
+            .tag::<NotFound>(3)
+            .tag::<UsageError>(2)
+            .code_of(&err),
+        3
+    );
+}
+
+#[test]
+fn terminates_with_exit_code() {
+    let report = ExitReport::new(run("missing.toml"))
+        .with_codes(codes())
+        .with_options(ReportOptions::short());
+
+    assert_eq!(report.report(), ExitCode::from(3));
+    assert_eq!(ExitReport::new(Ok(())).report(), ExitCode::SUCCESS);
+}
@@ -0,0 +1,68 @@
+use oofs::{oofs, Oof, OofExt};
+
+struct RetryTag;
+struct OtherTag;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    middle()._tag::<RetryTag>()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn middle() -> Result<(), Oof> {
+    inner()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner() -> Result<u64, Oof> {
+    let ret = "hello world"
+        .parse::<u64>()
+        ._tag::<RetryTag>()
+        ._attach("inner attachment")?;
+
+    Ok(ret)
+}
+
+#[test]
+fn finds_tagged_frames() {
+    let err = outer().unwrap_err();
+
+    let outermost = err.find_tagged::<RetryTag>().unwrap();
+    assert!(std::ptr::eq(outermost, &err));
+
+    let innermost = err.find_tagged_rev::<RetryTag>().unwrap();
+    assert!(innermost.to_string().starts_with("$0.parse() failed"));
+    assert!(format!("{innermost:?}").contains("inner attachment"));
+    assert_eq!(
+        innermost.root_cause().to_string(),
+        "invalid digit found in string"
+    );
+
+    assert!(err.find_tagged::<OtherTag>().is_none());
+    assert!(err.find_tagged_rev::<OtherTag>().is_none());
+}
+
+#[test]
+fn reports_depths() {
+    let err = outer().unwrap_err();
+
+    assert_eq!(err.tagged_depth::<RetryTag>(), Some(0));
+    assert_eq!(err.tagged_depth::<OtherTag>(), None);
+
+    let depths: Vec<_> = err.frames_tagged::<RetryTag>().map(|(d, _)| d).collect();
+    assert_eq!(depths, [0, 2]);
+
+    let depths: Vec<_> = err
+        .frames_tagged::<RetryTag>()
+        .rev()
+        .map(|(d, _)| d)
+        .collect();
+    assert_eq!(depths, [2, 0]);
+
+    let nested = err.find_source::<Oof>().unwrap();
+    assert_eq!(nested.tagged_depth::<RetryTag>(), Some(1));
+}
@@ -0,0 +1,81 @@
+use oofs::{oofs, report::Indented, Oof, OofExt};
+use std::fmt::Write;
+
+struct RetryTag;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner(123)?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner(x: u32) -> Result<u64, Oof> {
+    let ret = "hello world"
+        .parse::<u64>()
+        ._tag::<RetryTag>()
+        ._attach(x)
+        ._attach_lazy(|| "lazy")?;
+
+    Ok(ret)
+}
+
+#[test]
+fn iterates_frames_of_chain() {
+    let err = outer().unwrap_err();
+
+    let frames: Vec<_> = err.frames().collect();
+    assert_eq!(frames.len(), 3);
+
+    let depths: Vec<_> = frames.iter().map(|f| f.depth()).collect();
+    assert_eq!(depths, [0, 1, 2]);
+
+    assert!(frames[0].is_generated());
+    assert_eq!(frames[0].message().to_string(), "inner($0) failed");
+    assert!(!frames[0].is_debug());
+
+    let inner = &frames[1];
+    assert!(inner.oof().is_some());
+    assert_eq!(inner.tags().collect::<Vec<_>>(), ["frames::RetryTag"]);
+
+    let parameters: Vec<_> = inner.parameters().map(|p| p.to_string()).collect();
+    assert_eq!(parameters, ["$0: &str = \"hello world\""]);
+
+    let attachments: Vec<_> = inner.attachments().map(|a| a.to_string()).collect();
+    assert_eq!(attachments, ["123", "lazy"]);
+
+    let foreign = &frames[2];
+    assert!(foreign.oof().is_none());
+    assert!(!foreign.is_generated());
+    assert_eq!(
+        foreign.message().to_string(),
+        "invalid digit found in string"
+    );
+    assert_eq!(foreign.parameters().count(), 0);
+    assert_eq!(foreign.attachments().len(), 0);
+    assert_eq!(foreign.tags().count(), 0);
+}
+
+#[cfg(feature = "location")]
+#[test]
+fn exposes_location_of_frame() {
+    let (err, line) = (oofs::oof!("custom error"), line!());
+
+    let frame = err.frames().next().unwrap();
+    let location = frame.location().unwrap();
+
+    assert_eq!(location.file(), "oofs/tests/frames.rs");
+    assert_eq!(location.line(), line);
+}
+
+#[test]
+fn indents_lines() {
+    let mut s = String::new();
+    write!(Indented::new(&mut s), "first\nsecond").unwrap();
+    assert_eq!(s, "    first\n    second");
+
+    let mut s = String::new();
+    write!(Indented::numbered(&mut s, 12), "first\nsecond").unwrap();
+    assert_eq!(s, "   12: first\n       second");
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "http")]
+
+use oofs::*;
+
+struct InvalidInput;
+struct NotFound;
+struct Unauthorized;
+
+#[oofs]
+fn find_user(id: u64) -> Result<String, Oof> {
+    ensure!(id != 0, "user id {} is invalid", id, { tag: [InvalidInput] });
+
+    let token = "secret-token";
+    lookup(id, token)?;
+
+    Ok("admin".to_owned())
+}
+
+#[oofs]
+fn lookup(id: u64, token: &str) -> Result<(), Oof> {
+    ensure!(!token.is_empty(), { tag: [Unauthorized] });
+
+    oof!("no user {}", id)
+        .tag::<NotFound>()
+        .with_code("E-USER-404")
+        .attach(token)
+        .into_res()
+}
+
+fn statuses() -> HttpStatuses {
+    HttpStatuses::new()
+        .tag::<InvalidInput>(400, "invalid input")
+        .tag::<Unauthorized>(401, "unauthorized")
+        .tag::<NotFound>(404, "user not found")
+}
+
+#[test]
+fn maps_tags_to_public_errors() {
+    let err = find_user(0).unwrap_err();
+    assert_eq!(
+        statuses().public_error(&err),
+        PublicError {
+            status: 400,
+            message: "invalid input".into(),
+            code: None,
+        }
+    );
+
+    let err = find_user(42).unwrap_err();
+    let public = statuses().public_error(&err);
+    assert_eq!(public.status, 404);
+    assert_eq!(public.message, "user not found");
+    assert_eq!(public.code.as_deref(), Some("E-USER-404"));
+
+    // the public body has nothing from the report.
+    assert!(!format!("{public:?}").contains("secret-token"));
+    assert!(format!("{err:?}").contains("secret-token"));
+}
+
+#[test]
+fn maps_in_precedence_order() {
+    let err = oof!("both").tag::<NotFound>().tag::<Unauthorized>();
+
+    assert_eq!(statuses().public_error(&err).status, 401);
+
+    let untagged = oof!("db is down");
+    assert_eq!(statuses().public_error(&untagged).status, 500);
+    assert_eq!(
+        statuses()
+            .with_default(503, "unavailable")
+            .public_error(&untagged)
+            .message,
+        "unavailable"
+    );
+}
+
+#[test]
+fn uses_mapping_set_for_process() {
+    // every test in this file sets the same mapping.
+    let _ = set_http_mapping(statuses());
+
+    assert_eq!(find_user(0).unwrap_err().public_error().status, 400);
+    assert!(set_http_mapping(HttpStatuses::new()).is_err());
+    assert_eq!(find_user(42).unwrap_err().public_error().status, 404);
+}
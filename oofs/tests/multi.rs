@@ -0,0 +1,107 @@
+use oofs::{oof, oofs, report::ReportOptions, validate, Oof, OofExt, OofIteratorExt, Validator};
+use std::{error::Error, num::ParseIntError};
+
+struct InvalidInput;
+
+#[oofs]
+fn parse(text: &str) -> Result<u64, Oof> {
+    let ret = text.parse::<u64>()._tag::<InvalidInput>()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn parse_all(texts: &[&str]) -> Result<Vec<u64>, Oof> {
+    let ret = texts.iter().map(|t: &&str| parse(t)).collect_all()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn validate_user(name: &str, age: &str) -> Result<u64, Oof> {
+    let mut v = Validator::new();
+
+    validate!(v, !name.is_empty(), "name is empty");
+    validate!(v, name.len() < 8, "name {:?} is too long", name, {
+        code: "E-NAME-LEN"
+    });
+    let age = v.check(parse(age));
+
+    v.finish()?;
+
+    Ok(age.unwrap())
+}
+
+#[test]
+fn collects_every_error() {
+    assert_eq!(parse_all(&["1", "2"]).unwrap(), [1, 2]);
+
+    let err = parse_all(&["1", "a", "2", "b"]).unwrap_err();
+    let multi = err.find_source::<Oof>().unwrap();
+
+    assert_eq!(multi.causes().len(), 2);
+    assert!(multi.to_string().starts_with("2 errors occurred"));
+    assert!(multi.source().is_none());
+}
+
+#[test]
+fn validates_every_check() {
+    assert_eq!(validate_user("name", "12").unwrap(), 12);
+
+    let err = validate_user("", "x").unwrap_err();
+    let causes = err.find_source::<Oof>().unwrap().causes();
+
+    assert_eq!(causes.len(), 2);
+    assert!(causes[0].to_string().starts_with("name is empty"));
+    assert!(causes[1].tagged_nested::<InvalidInput>());
+
+    let err = validate_user("too long name", "x").unwrap_err();
+    let causes = err.find_source::<Oof>().unwrap().causes();
+
+    assert_eq!(causes.len(), 2);
+    assert_eq!(causes[0].code(), Some("E-NAME-LEN"));
+}
+
+#[test]
+fn searches_every_branch() {
+    let err = Oof::multi([oof!("first error"), parse("x").unwrap_err()]);
+
+    assert!(!err.tagged::<InvalidInput>());
+    assert!(err.tagged_nested::<InvalidInput>());
+    assert_eq!(err.tagged_depth::<InvalidInput>(), Some(1));
+    assert!(err.find_source::<ParseIntError>().is_some());
+    assert!(err.downcast_ref::<ParseIntError>().is_some());
+
+    let names: Vec<_> = err
+        .frames_tagged::<InvalidInput>()
+        .map(|(depth, e)| (depth, e.to_string()))
+        .collect();
+    assert_eq!(names.len(), 1);
+    assert!(names[0].1.starts_with("text.parse() failed"));
+
+    assert_eq!(
+        err.find_tagged_rev::<InvalidInput>().map(Oof::to_string),
+        Some(names[0].1.clone())
+    );
+}
+
+#[test]
+fn draws_causes_as_tree() {
+    let err = Oof::multi([oof!("first error"), oof!("second error").attach(1u8)]);
+    let err = oofs::wrap_err(err);
+
+    let debug = format!("{err:?}");
+    let errors = debug.find("    Errors:\n").unwrap();
+    let first = debug.find("        0: first error at `").unwrap();
+    let second = debug.find("        1: second error at `").unwrap();
+
+    assert!(errors < first && first < second);
+    assert!(debug[second..].contains("           Attachments:\n               0: 1"));
+
+    let compact = err.report(&ReportOptions::short()).to_string();
+
+    assert!(!compact.contains('\n'));
+    assert!(compact.contains(": [first error at `"));
+    assert!(compact.contains("`; second error at `"));
+    assert!(compact.ends_with("`]"));
+}
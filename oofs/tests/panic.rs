@@ -0,0 +1,106 @@
+#![cfg(all(feature = "std", feature = "location"))]
+
+use oofs::*;
+use std::{error::Error, thread};
+
+#[oofs(catch_panics)]
+fn divide(a: u64, b: u64) -> Result<u64, Oof> {
+    let parsed = "10".parse::<u64>()?;
+
+    Ok(parsed * a / b)
+}
+
+#[oofs(catch_panics)]
+fn first(items: &[String]) -> Result<&str, Oof> {
+    Ok(&items[0])
+}
+
+struct Worker;
+
+#[oofs]
+impl Worker {
+    #[oofs(catch_panics)]
+    fn run(&self, items: &[u64]) -> Result<u64, Oof> {
+        if items.is_empty() {
+            return oof!("no items").into_res();
+        }
+
+        Ok(items[5])
+    }
+}
+
+#[oofs]
+fn spawn_worker() -> Result<u64, Oof> {
+    let handle = thread::spawn(|| -> u64 { panic!("worker {} died", 3) });
+
+    let value = handle.join()?;
+
+    Ok(value)
+}
+
+#[oofs(catch_panics)]
+fn digits(text: &str) -> Result<impl Iterator<Item = u32> + '_, Oof> {
+    assert!(!text.is_empty(), "no digits");
+    let limit = "3".parse::<usize>()?;
+
+    Ok(text.chars().take(limit).filter_map(|c| c.to_digit(10)))
+}
+
+#[test]
+fn catches_panics() {
+    let line = line!() + 1;
+    let err = catch_unwind(|| -> u64 { panic!("boom") }).unwrap_err();
+
+    assert!(err.tagged::<Panic>());
+    assert_eq!(err.tag_infos().next().unwrap().name(), "Panic");
+    assert_eq!(err.severity(), Some(Severity::Critical));
+
+    assert_eq!(
+        err.to_string(),
+        format!("panicked: boom at `{}:{line}:40`", file!())
+    );
+
+    assert_eq!(catch_unwind(|| 5).unwrap(), 5);
+}
+
+#[test]
+fn catches_panics_in_instrumented_fns() {
+    assert_eq!(divide(4, 2).unwrap(), 20);
+
+    let err = divide(4, 0).unwrap_err();
+    assert!(err.tagged::<Panic>());
+    assert_eq!(
+        err.to_string(),
+        format!("panicked: attempt to divide by zero at `{}:10:8`", file!())
+    );
+
+    assert_eq!(first(&["a".to_owned()]).unwrap(), "a");
+    assert!(first(&[]).unwrap_err().tagged::<Panic>());
+
+    let err = Worker.run(&[1, 2]).unwrap_err();
+    assert!(err.tagged::<Panic>());
+    assert!(err.to_string().contains("index out of bounds"));
+
+    // errors returned by the function are kept as they are.
+    let err = Worker.run(&[]).unwrap_err();
+    assert!(!err.tagged_nested::<Panic>());
+    assert!(err.to_string().starts_with("no items"));
+}
+
+#[test]
+fn catches_panics_in_fns_returning_impl_trait() {
+    assert_eq!(digits("1234").unwrap().collect::<Vec<_>>(), [1, 2, 3]);
+
+    let err = digits("").err().unwrap();
+    assert!(err.tagged::<Panic>());
+    assert!(err.to_string().starts_with("panicked: no digits"));
+}
+
+#[test]
+fn bubbles_up_thread_panics() {
+    let err = spawn_worker().unwrap_err();
+
+    assert!(err.tagged::<Panic>());
+    assert!(err.to_string().starts_with("handle.join() failed at `"));
+    assert_eq!(err.source().unwrap().to_string(), "worker 3 died");
+}
@@ -0,0 +1,121 @@
+use oofs::{oofs, report::ReportOptions, Oof, OofExt};
+
+#[oofs]
+fn parse(depth: u32) -> Result<u64, Oof> {
+    if depth == 0 {
+        return Ok("hello world".parse::<u64>()?);
+    }
+
+    let ret = parse(depth - 1)?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn outer(text: &str) -> Result<u64, Oof> {
+    let ret = middle(text)?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn middle(text: &str) -> Result<u64, Oof> {
+    let ret = inner(text, 10)?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn inner(text: &str, radix: u32) -> Result<u64, Oof> {
+    let ret = u64::from_str_radix(text, radix)?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn countdown(depth: u32) -> Result<u64, Oof> {
+    if depth == 0 {
+        return Ok("hello world".parse::<u64>()?);
+    }
+
+    let ret = countdown(depth - 1)._attach(depth)?;
+
+    Ok(ret)
+}
+
+fn collapsed() -> ReportOptions {
+    ReportOptions::new().with_collapse_repeats(true)
+}
+
+#[test]
+fn collapses_repeated_frames() {
+    let err = parse(12).unwrap_err();
+    let debug = err.report(&collapsed()).to_string();
+
+    assert!(debug.starts_with("parse($0) failed at `oofs/tests/repeated_frames.rs:9:15`"));
+    assert!(debug
+        .contains("\nCaused by:\n    [frame repeated 11 times]\n   11: $0.parse() failed at `"));
+    assert!(debug.contains("\n   12: invalid digit found in string"));
+    assert_eq!(debug.matches("parse($0) failed").count(), 1);
+}
+
+#[test]
+fn collapses_repeated_frames_in_compact_report() {
+    let err = parse(2).unwrap_err();
+
+    let report = err
+        .report(&ReportOptions::short().with_collapse_repeats(true))
+        .to_string();
+
+    assert!(report.starts_with("parse($0) failed at `oofs/tests/repeated_frames.rs:9:15`: [frame repeated 1 time]: $0.parse() failed at `"));
+    assert!(report.ends_with("`: invalid digit found in string"));
+}
+
+#[test]
+fn keeps_frames_with_different_attachments() {
+    let err = countdown(3).unwrap_err();
+    let debug = err.report(&collapsed()).to_string();
+
+    assert!(!debug.contains("[frame repeated"));
+    assert_eq!(debug.matches("countdown($0) failed").count(), 3);
+    assert!(debug.contains("Attachments:\n           0: 2"));
+}
+
+#[test]
+fn keeps_repeated_frames_by_default() {
+    let err = parse(2).unwrap_err();
+
+    let report = format!("{err:?}");
+
+    assert!(!report.contains("[frame repeated"));
+    assert_eq!(report.matches("parse($0) failed").count(), 2);
+    assert!(report.contains("    0: parse($0) failed at `"));
+    assert!(report.contains("    1: $0.parse() failed at `"));
+}
+
+#[test]
+fn counts_repeats_within_max_depth() {
+    let err = parse(12).unwrap_err();
+
+    let report = err.report(&collapsed().with_max_depth(5)).to_string();
+
+    assert!(report.contains("\nCaused by:\n    [frame repeated 4 times]\n    ... 9 more frames"));
+}
+
+#[test]
+fn merges_parameters_shown_in_inner_frame() {
+    let err = outer("hello world").unwrap_err();
+
+    let merged = err
+        .report(&ReportOptions::new().with_merge_parameters(true))
+        .to_string();
+    let full = err.report(&ReportOptions::new()).to_string();
+
+    assert_eq!(full.matches("= \"hello world\"").count(), 3);
+    // parameters of `middle($0)` and `inner($0, $1)` are shown in `u64::from_str_radix($0, $1)`.
+    assert_eq!(merged.matches("= \"hello world\"").count(), 1);
+    assert!(merged
+        .starts_with("middle($0) failed at `oofs/tests/repeated_frames.rs:16:15`\n\nCaused by:\n"));
+    assert!(merged.contains("    1: u64::from_str_radix($0, $1) failed at `"));
+    assert!(merged.contains("$1: u32 = 10"));
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "std")]
+
+use oofs::{oof, report::ReportOptions, OofExt};
+
+#[test]
+fn reads_detail_from_env() {
+    // set before any `Oof` is debugged, since the options are read once per process.
+    std::env::set_var("OOFS_DETAIL", "short");
+
+    let options = ReportOptions::from_env();
+    assert!(options.compact());
+    assert!(options.short_types());
+
+    let err = "hello world"
+        .parse::<u64>()
+        ._attach(1u8)
+        .map_err(|e| oof!("custom error").attach(e))
+        .unwrap_err();
+
+    let report = format!("{err:?}");
+    assert!(!report.contains('\n'));
+    assert!(report.starts_with("custom error at `oofs/tests/report_env.rs:"));
+
+    std::env::set_var("OOFS_DETAIL", "full");
+    assert!(!ReportOptions::from_env().compact());
+}
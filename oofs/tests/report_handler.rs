@@ -0,0 +1,85 @@
+use oofs::{
+    oofs,
+    report::{Frame, ReportHandler},
+    Oof, OofExt,
+};
+use std::{fmt, sync::Once};
+
+struct RetryTag;
+
+/// Renders each frame in one line, leaving the layout of the chain to the default `debug`.
+struct OneLine;
+
+impl ReportHandler for OneLine {
+    fn display(&self, frame: &Frame<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", frame.depth(), frame.message())?;
+
+        for parameter in frame.parameters() {
+            write!(f, " ({}: {})", parameter.index(), parameter.ty())?;
+        }
+
+        for attachment in frame.attachments() {
+            write!(f, " +{attachment}")?;
+        }
+
+        if frame.is_debug() {
+            for tag in frame.tags() {
+                write!(f, " #{tag}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn install() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| oofs::set_handler(OneLine).unwrap());
+}
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner(123)._attach("outer")?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner(x: u32) -> Result<u64, Oof> {
+    let ret = "hello world".parse::<u64>()._tag::<RetryTag>()._attach(x)?;
+
+    Ok(ret)
+}
+
+#[test]
+fn renders_display_with_handler() {
+    install();
+
+    let err = oofs::oof!("custom error").attach(1u8);
+
+    assert_eq!(err.to_string(), "[0] custom error +1");
+}
+
+#[test]
+fn renders_debug_with_handler_for_each_frame() {
+    install();
+
+    let err = outer().unwrap_err();
+
+    // backtrace and span trace may follow the frames, depending on features.
+    assert!(format!("{err:?}").starts_with(
+        "[0] inner($0) failed (0: u32) +\"outer\"\n\
+         Caused by:\n    \
+         0: [1] $0.parse() failed (0: &str) +123 #report_handler::RetryTag\n    \
+         1: [2] invalid digit found in string"
+    ));
+}
+
+#[test]
+fn rejects_second_handler() {
+    install();
+
+    let err = oofs::set_handler(OneLine).unwrap_err();
+
+    assert_eq!(err.to_string(), "report handler is already set");
+}
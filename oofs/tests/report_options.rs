@@ -0,0 +1,114 @@
+use oofs::{oofs, report::ReportOptions, Oof, OofExt};
+
+struct RetryTag;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    middle(String::from("hello world"))?;
+
+    Ok(())
+}
+
+#[oofs]
+fn middle(text: String) -> Result<u64, Oof> {
+    let ret = inner(&text)._attach("middle")?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn inner(text: &str) -> Result<u64, Oof> {
+    let ret = text.trim().to_owned().parse::<u64>()._tag::<RetryTag>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn default_options_match_debug() {
+    let err = outer().unwrap_err();
+
+    assert_eq!(
+        err.report(&ReportOptions::new()).to_string(),
+        format!("{err:?}")
+    );
+}
+
+#[test]
+fn renders_compact_report_in_single_line() {
+    let err = outer().unwrap_err();
+
+    let report = err
+        .report(&ReportOptions::new().with_compact(true))
+        .to_string();
+
+    assert!(!report.contains('\n'));
+    assert!(report.starts_with("middle($0) failed at `oofs/tests/report_options.rs:"));
+    assert!(report.contains(": text.trim().to_owned().parse() failed at `"));
+    assert!(report.ends_with("`: invalid digit found in string"));
+    assert!(!report.contains("Attachments:"));
+}
+
+#[test]
+fn limits_depth_of_report() {
+    let err = outer().unwrap_err();
+
+    let report = err
+        .report(&ReportOptions::new().with_max_depth(2))
+        .to_string();
+
+    assert!(report.contains("Caused by:\n    0: inner($0) failed"));
+    assert!(!report.contains("    1: "));
+    // backtrace and span trace may follow the frames, depending on features.
+    assert!(report.contains("\n    ... 2 more frames"));
+
+    let report = err
+        .report(&ReportOptions::new().with_compact(true).with_max_depth(0))
+        .to_string();
+
+    assert!(report.starts_with("middle($0) failed"));
+    assert!(report.ends_with(": ... 3 more frames"));
+}
+
+#[test]
+fn shortens_type_names() {
+    let err = outer().unwrap_err();
+
+    let full = err.report(&ReportOptions::new()).to_string();
+    assert!(full.contains("$0: alloc::string::String = \"hello world\""));
+    assert!(full.contains("    report_options::RetryTag\n"));
+
+    let short = err
+        .report(&ReportOptions::new().with_short_types(true))
+        .to_string();
+    assert!(short.contains("$0: String = \"hello world\""));
+    assert!(short.contains("$0: &String = \"hello world\""));
+    assert!(short.contains("    RetryTag\n"));
+}
+
+#[test]
+fn hides_parameters_and_attachments() {
+    let err = outer().unwrap_err();
+
+    let report = err
+        .report(
+            &ReportOptions::new()
+                .with_parameters(false)
+                .with_attachments(false),
+        )
+        .to_string();
+
+    assert!(!report.contains("Parameters:"));
+    assert!(!report.contains("Attachments:"));
+    assert!(report.contains("Tags:"));
+}
+
+#[test]
+fn renders_root_source_with_debug() {
+    let err = outer().unwrap_err();
+
+    let report = err
+        .report(&ReportOptions::new().with_root_debug(true))
+        .to_string();
+
+    assert!(report.contains("    2: ParseIntError { kind: InvalidDigit }"));
+}
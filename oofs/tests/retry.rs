@@ -0,0 +1,171 @@
+#![cfg(feature = "std")]
+
+use oofs::*;
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    time::{Duration, SystemTime},
+};
+
+struct RetryTag;
+
+#[oofs]
+fn flaky(calls: &Cell<usize>, succeed_at: usize) -> Result<usize, Oof> {
+    calls.set(calls.get() + 1);
+
+    ensure!(
+        calls.get() >= succeed_at,
+        "call {} failed",
+        calls.get(),
+        { tag: [RetryTag] }
+    );
+
+    Ok(calls.get())
+}
+
+#[oofs]
+fn broken() -> Result<(), Oof> {
+    oof!("broken").into_res()
+}
+
+fn block_on<F: Future>(fut: F) -> F::Output {
+    struct Noop;
+    impl Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(Noop));
+    let mut cx = Context::from_waker(&waker);
+    let mut fut = pin!(fut);
+
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+            return out;
+        }
+    }
+}
+
+fn policy(attempts: usize) -> RetryPolicy {
+    RetryPolicy::new(attempts)
+        .with_delay(Duration::ZERO)
+        .retry_on::<RetryTag>()
+}
+
+#[test]
+fn retries_until_success() {
+    let calls = Cell::new(0);
+
+    let res = retry(&policy(3), || flaky(&calls, 3));
+
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(calls.get(), 3);
+}
+
+#[test]
+fn keeps_every_attempt() {
+    let calls = Cell::new(0);
+    let before = SystemTime::now();
+
+    let err = retry(&policy(3), || flaky(&calls, 10)).unwrap_err();
+
+    assert_eq!(calls.get(), 3);
+    assert!(err.to_string().starts_with("retried 2 times at `"));
+    assert_eq!(err.causes().len(), 3);
+    assert!(err.causes()[2].to_string().starts_with("call 3 failed"));
+
+    let attempts: Vec<_> = err.attachments_of::<Attempt>().collect();
+    assert_eq!(
+        attempts.iter().map(|a| a.number).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert!(attempts.iter().all(|a| a.failed_at >= before));
+    assert!(attempts
+        .windows(2)
+        .all(|w| w[0].failed_at <= w[1].failed_at));
+
+    let report = format!("{err:?}");
+    assert!(report.contains("\nErrors:\n"));
+    assert!(report.contains("attempt 3 failed at 20"));
+}
+
+#[test]
+fn returns_errors_not_tagged_as_is() {
+    let calls = Cell::new(0);
+
+    let err = retry(&policy(3), || {
+        calls.set(calls.get() + 1);
+        broken()
+    })
+    .unwrap_err();
+
+    assert_eq!(calls.get(), 1);
+    assert!(err.to_string().starts_with("broken at `"));
+    assert!(err.causes().is_empty());
+}
+
+#[test]
+fn retries_every_error_without_tags() {
+    let calls = Cell::new(0);
+    let policy = RetryPolicy::new(2).with_delay(Duration::ZERO);
+
+    let err = retry(&policy, || {
+        calls.set(calls.get() + 1);
+        broken()
+    })
+    .unwrap_err();
+
+    assert_eq!(calls.get(), 2);
+    assert!(err.to_string().starts_with("retried 1 time at `"));
+}
+
+#[test]
+fn retries_async_with_backoff() {
+    let calls = Cell::new(0);
+    let delays = RefCell::new(Vec::new());
+
+    let policy = RetryPolicy::new(5)
+        .with_delay(Duration::from_millis(10))
+        .with_backoff(2.0)
+        .with_max_delay(Duration::from_millis(30))
+        .retry_on::<RetryTag>();
+
+    let sleep = |delay| {
+        delays.borrow_mut().push(delay);
+        async {}
+    };
+
+    let res = block_on(retry_async(&policy, sleep, || async { flaky(&calls, 5) }));
+
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(
+        *delays.borrow(),
+        [10, 20, 30, 30].map(Duration::from_millis)
+    );
+}
+
+#[test]
+fn jitter_shortens_delays() {
+    let delays = RefCell::new(Vec::new());
+
+    let policy = RetryPolicy::new(20)
+        .with_delay(Duration::from_millis(100))
+        .with_backoff(1.0)
+        .with_jitter(0.5);
+
+    let sleep = |delay| {
+        delays.borrow_mut().push(delay);
+        async {}
+    };
+
+    let err = block_on(retry_async(&policy, sleep, || async { broken() })).unwrap_err();
+
+    assert!(err.to_string().starts_with("retried 19 times"));
+    assert!(delays
+        .borrow()
+        .iter()
+        .all(|d| (50..=100).contains(&d.as_millis())));
+    assert!(delays.borrow().windows(2).any(|w| w[0] != w[1]));
+}
@@ -0,0 +1,237 @@
+#![cfg(feature = "serde")]
+
+use oofs::{oof, oofs, Oof, OofExt, Severity};
+
+struct RetryTag;
+
+// Backtraces are not serialized, so compare reports without them.
+fn report(err: &Oof) -> String {
+    let report = format!("{err:?}");
+
+    match report.find("\n\nStack backtrace:") {
+        Some(i) => report[..i].to_owned(),
+        None => report,
+    }
+}
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner("hello world")?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner(text: &str) -> Result<u64, Oof> {
+    let ret = text.parse::<u64>()._tag::<RetryTag>()._attach(123u8)?;
+
+    Ok(ret)
+}
+
+#[test]
+fn serializes_nested_chain() {
+    let err = outer().unwrap_err();
+
+    let json = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(json["version"], oofs::SCHEMA_VERSION);
+
+    let frames = json["frames"].as_array().unwrap();
+    assert_eq!(frames.len(), 3);
+
+    let outer = &frames[0]["oof"];
+    assert_eq!(outer["message"], "inner($0) failed");
+    let receiver = &outer["context"]["generated"]["receiver"]["method"];
+    assert_eq!(receiver["name"], "inner");
+    assert_eq!(receiver["args"][0]["index"], 0);
+    assert_eq!(receiver["args"][0]["ty"], "&str");
+    assert_eq!(receiver["args"][0]["value"], "\"hello world\"");
+    assert!(outer["tags"].as_array().unwrap().is_empty());
+
+    let inner = &frames[1]["oof"];
+    assert_eq!(
+        inner["context"]["generated"]["receiver"]["ident"]["name"],
+        "text"
+    );
+    assert_eq!(inner["context"]["generated"]["chain"][0]["name"], "parse");
+    assert_eq!(inner["tags"][0], "serialize::RetryTag");
+    assert_eq!(inner["attachments"][0], "123");
+
+    #[cfg(feature = "location")]
+    assert_eq!(inner["location"]["file"], "oofs/tests/serialize.rs");
+
+    assert_eq!(
+        frames[2]["foreign"]["message"],
+        "invalid digit found in string"
+    );
+}
+
+#[test]
+fn serializes_custom_context() {
+    let err = oofs::oof!("custom error {}", 1);
+
+    let json = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(
+        json["frames"][0]["oof"]["context"]["custom"],
+        "custom error 1"
+    );
+}
+
+struct RegisteredTag;
+
+#[oofs]
+fn registered() -> Result<(), Oof> {
+    inner("hello world")._tag::<RegisteredTag>()?;
+
+    Ok(())
+}
+
+#[test]
+fn deserializes_json_identically() {
+    oofs::register_tag::<RegisteredTag>("registered");
+
+    let err = registered().unwrap_err();
+
+    let json = serde_json::to_string(&err).unwrap();
+    assert!(json.contains("\"registered\""));
+
+    let rebuilt: Oof = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(report(&rebuilt), report(&err));
+    assert_eq!(rebuilt.to_string(), err.to_string());
+    assert!(rebuilt.tagged::<RegisteredTag>());
+    // `RetryTag` is not registered on this side, so it is only kept by name.
+    assert!(!rebuilt.tagged_nested::<RetryTag>());
+
+    let json2 = serde_json::to_string(&rebuilt).unwrap();
+    assert_eq!(json, json2);
+}
+
+#[test]
+fn deserializes_remote_sources() {
+    let err = outer().unwrap_err();
+
+    let rebuilt: Oof = serde_json::from_value(serde_json::to_value(&err).unwrap()).unwrap();
+
+    let remote = std::iter::successors(Some(&rebuilt as &(dyn std::error::Error + 'static)), |e| {
+        e.source()
+    })
+    .find_map(|e| e.downcast_ref::<oofs::RemoteError>())
+    .unwrap();
+
+    assert_eq!(remote.message(), "invalid digit found in string");
+}
+
+#[test]
+fn deserializes_binary_identically() {
+    let err = outer().unwrap_err();
+
+    let bytes = postcard::to_allocvec(&err).unwrap();
+    let rebuilt: Oof = postcard::from_bytes(&bytes).unwrap();
+
+    assert_eq!(report(&rebuilt), report(&err));
+}
+
+#[test]
+fn rejects_newer_schema() {
+    let mut json = serde_json::to_value(oofs::oof!("custom error")).unwrap();
+    json["version"] = (oofs::SCHEMA_VERSION + 1).into();
+
+    assert!(serde_json::from_value::<Oof>(json).is_err());
+}
+
+#[test]
+fn round_trips_code_and_severity() {
+    let err = oof!("custom error")
+        .with_code("E-STORAGE-042")
+        .with_severity(Severity::Critical);
+
+    let json = serde_json::to_value(&err).unwrap();
+
+    assert_eq!(json["frames"][0]["oof"]["code"], "E-STORAGE-042");
+    assert_eq!(json["frames"][0]["oof"]["severity"], "critical");
+
+    let rebuilt: Oof = serde_json::from_value(json).unwrap();
+
+    assert_eq!(rebuilt.code(), Some("E-STORAGE-042"));
+    assert_eq!(rebuilt.severity(), Some(Severity::Critical));
+}
+
+#[test]
+fn accepts_reports_without_code_and_severity() {
+    let mut json = serde_json::to_value(oof!("custom error")).unwrap();
+
+    json["version"] = 1.into();
+    let frame = json["frames"][0]["oof"].as_object_mut().unwrap();
+    frame.remove("code");
+    frame.remove("severity");
+
+    let rebuilt: Oof = serde_json::from_value(json).unwrap();
+
+    assert_eq!(rebuilt.code(), None);
+    assert_eq!(rebuilt.severity(), None);
+}
+
+#[test]
+fn round_trips_multi() {
+    struct BranchTag;
+    oofs::register_tag::<BranchTag>("branch");
+
+    let err = Oof::multi([oof!("first error"), outer().unwrap_err().tag::<BranchTag>()]);
+
+    let json = serde_json::to_value(&err).unwrap();
+
+    let causes = json["frames"][0]["oof"]["causes"].as_array().unwrap();
+    assert_eq!(causes.len(), 2);
+    assert_eq!(causes[0][0]["oof"]["message"], "first error");
+    assert_eq!(causes[1].as_array().unwrap().len(), 3);
+
+    let rebuilt: Oof = serde_json::from_value(json).unwrap();
+
+    assert_eq!(rebuilt.causes().len(), 2);
+    assert!(rebuilt.tagged_nested::<BranchTag>());
+    assert_eq!(rebuilt.to_string(), err.to_string());
+
+    for (rebuilt, cause) in rebuilt.causes().iter().zip(err.causes()) {
+        assert_eq!(report(rebuilt), report(cause));
+    }
+}
+
+#[cfg(feature = "location")]
+#[test]
+fn rebuilds_locations_in_distinct_files() {
+    let json = serde_json::to_value(oof!("custom error")).unwrap();
+
+    for i in 0..1000 {
+        let file = format!("remote/file_{i}.rs");
+
+        let mut json = json.clone();
+        json["frames"][0]["oof"]["location"]["file"] = file.as_str().into();
+
+        let rebuilt: Oof = serde_json::from_value(json).unwrap();
+
+        let frame = rebuilt.frames().next().unwrap();
+        assert_eq!(frame.location().unwrap().file(), file);
+    }
+}
+
+#[test]
+fn rejects_deeply_nested_causes() {
+    fn nested(depth: usize) -> serde_json::Value {
+        let mut json = serde_json::to_value(oof!("custom error")).unwrap();
+
+        for _ in 0..depth {
+            let mut outer = serde_json::to_value(oof!("custom error")).unwrap();
+            outer["frames"][0]["oof"]["causes"] = serde_json::json!([json["frames"].take()]);
+            json = outer;
+        }
+
+        json
+    }
+
+    assert!(serde_json::from_value::<Oof>(nested(100)).is_ok());
+
+    let err = serde_json::from_value::<Oof>(nested(200)).unwrap_err();
+    assert!(err.to_string().contains("deeper than 128 levels"));
+}
@@ -0,0 +1,18 @@
+use core::mem::size_of;
+use oofs::Oof;
+
+#[test]
+fn oof_is_a_thin_pointer() {
+    assert_eq!(size_of::<Oof>(), size_of::<usize>());
+    assert_eq!(size_of::<Option<Oof>>(), size_of::<usize>());
+}
+
+#[test]
+fn result_is_small() {
+    assert_eq!(size_of::<Result<(), Oof>>(), size_of::<usize>());
+    assert_eq!(size_of::<Result<u64, Oof>>(), 2 * size_of::<usize>());
+    assert_eq!(
+        size_of::<Result<(), Oof>>(),
+        size_of::<Result<(), Box<dyn std::error::Error + Send + Sync>>>() / 2
+    );
+}
@@ -0,0 +1,103 @@
+use oofs::{oofs, Oof, OofExt};
+use std::{error::Error, fmt, rc::Rc};
+
+struct RetryTag;
+
+fn unit_err() -> Result<u64, ()> {
+    Err(())
+}
+
+fn str_err() -> Result<u64, &'static str> {
+    Err("static str error")
+}
+
+fn string_err() -> Result<u64, String> {
+    Err("string error".to_owned())
+}
+
+#[derive(Debug)]
+struct RcError(Rc<str>);
+
+impl fmt::Display for RcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rc error: {}", self.0)
+    }
+}
+
+impl Error for RcError {}
+
+fn rc_err() -> Result<u64, RcError> {
+    Err(RcError("not send".into()))
+}
+
+#[allow(dead_code)]
+#[derive(Debug)]
+struct OnlyDebug {
+    code: u8,
+}
+
+fn debug_err() -> Result<u64, OnlyDebug> {
+    Err(OnlyDebug { code: 3 })
+}
+
+#[oofs]
+fn from_unit() -> Result<u64, Oof> {
+    let ret = unit_err()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn from_str() -> Result<u64, Oof> {
+    let ret = str_err()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn from_string() -> Result<u64, Oof> {
+    let ret = string_err()._tag::<RetryTag>()?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn from_rc() -> Result<u64, Oof> {
+    let ret = rc_err()._attach("not send")?;
+
+    Ok(ret)
+}
+
+#[oofs]
+fn from_debug() -> Result<u64, Oof> {
+    let ret = debug_err()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn snapshots_errors_through_debug() {
+    let err = from_unit().unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), "()");
+
+    let err = from_debug().unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), "OnlyDebug { code: 3 }");
+}
+
+#[test]
+fn converts_strings_into_sources() {
+    let err = from_str().unwrap_err();
+    assert_eq!(err.source().unwrap().to_string(), "static str error");
+
+    let err = from_string().unwrap_err();
+    assert!(err.tagged::<RetryTag>());
+    assert_eq!(err.source().unwrap().to_string(), "string error");
+}
+
+#[test]
+fn snapshots_non_send_errors_through_display() {
+    let err = from_rc().unwrap_err();
+
+    assert_eq!(err.source().unwrap().to_string(), "rc error: not send");
+    assert!(format!("{err:?}").contains("not send"));
+}
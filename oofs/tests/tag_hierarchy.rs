@@ -0,0 +1,114 @@
+#![cfg(feature = "tag_registry")]
+
+use oofs::{oofs, Oof, OofExt, Severity, Tag, TagInfo};
+use std::any::TypeId;
+
+#[derive(Tag)]
+struct Retryable;
+
+#[derive(Tag)]
+#[tag(parent(Retryable), description = "error that may succeed on retry")]
+struct Transient;
+
+#[derive(Tag)]
+#[tag(parent(Transient), name = "connection reset", severity = Warning)]
+struct ConnectionReset;
+
+struct PlainTag;
+
+#[derive(Tag)]
+#[tag(parent(PlainTag, Transient))]
+struct Throttled;
+
+// tags with cyclic parents must not hang the lookup.
+#[derive(Tag)]
+#[tag(parent(CycleB))]
+struct CycleA;
+
+#[derive(Tag)]
+#[tag(parent(CycleA))]
+struct CycleB;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner() -> Result<u64, Oof> {
+    let ret = "hello world".parse::<u64>()._tag::<ConnectionReset>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn matches_ancestors() {
+    let err = outer().unwrap_err();
+
+    assert!(err.tagged_nested::<ConnectionReset>());
+    assert!(err.tagged_nested::<Transient>());
+    assert!(err.tagged_nested::<Retryable>());
+    assert!(!err.tagged_nested::<Throttled>());
+
+    let err = oofs::oof!("custom error").tag::<Transient>();
+
+    assert!(err.tagged::<Retryable>());
+    assert!(!err.tagged::<ConnectionReset>());
+}
+
+#[test]
+fn keeps_plain_tags_working() {
+    let err = oofs::oof!("custom error").tag::<Throttled>();
+
+    assert!(err.tagged::<PlainTag>());
+    assert!(err.tagged::<Retryable>());
+
+    let err = oofs::oof!("custom error").tag::<PlainTag>();
+
+    assert!(err.tagged::<PlainTag>());
+    assert!(!err.tagged::<Throttled>());
+}
+
+#[test]
+fn tags_ancestors_separately() {
+    // tagging a parent after its descendant is not deduplicated away.
+    let err = oofs::oof!("custom error")
+        .tag::<ConnectionReset>()
+        .tag::<Transient>();
+
+    assert_eq!(err.tag_infos().count(), 2);
+}
+
+#[test]
+fn exposes_metadata() {
+    let err = oofs::oof!("custom error")
+        .tag::<ConnectionReset>()
+        .tag::<PlainTag>();
+
+    let infos: Vec<_> = err.tag_infos().collect();
+    assert_eq!(infos.len(), 1);
+
+    let info = infos[0];
+    assert_eq!(info.name(), "connection reset");
+    assert_eq!(info.severity(), Some(Severity::Warning));
+    assert_eq!(info.description(), None);
+    assert_eq!(
+        info.parents().collect::<Vec<_>>(),
+        [TypeId::of::<Transient>()]
+    );
+
+    let info = TagInfo::get(TypeId::of::<Transient>()).unwrap();
+    assert_eq!(info.name(), "Transient");
+    assert_eq!(info.description(), Some("error that may succeed on retry"));
+    assert_eq!(<Transient as Tag>::SEVERITY, None);
+}
+
+#[test]
+fn handles_cycles() {
+    let err = oofs::oof!("custom error").tag::<CycleA>();
+
+    assert!(err.tagged::<CycleB>());
+    assert!(!err.tagged::<Retryable>());
+}
@@ -0,0 +1,60 @@
+use oofs::{oofs, Oof, OofExt};
+
+struct RetryTag;
+struct OtherTag;
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner()._tag::<OtherTag>()?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner() -> Result<u64, Oof> {
+    let ret = "hello world".parse::<u64>()._tag::<RetryTag>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn lists_tag_names() {
+    let err = outer().unwrap_err();
+
+    assert_eq!(err.tag_names().collect::<Vec<_>>(), ["tag_names::OtherTag"]);
+
+    let nested = err.find_source::<Oof>().unwrap();
+    assert_eq!(
+        nested.tag_names().collect::<Vec<_>>(),
+        ["tag_names::RetryTag"]
+    );
+}
+
+#[test]
+fn prints_tags_per_frame_in_debug_report() {
+    let err = outer().unwrap_err();
+
+    let report = format!("{err:?}");
+
+    let outer_tags = report.find("Tags:\n    tag_names::OtherTag\n").unwrap();
+    let caused_by = report.find("Caused by:").unwrap();
+    let inner_tags = report
+        .find("Tags:\n           tag_names::RetryTag\n")
+        .unwrap();
+
+    assert!(outer_tags < caused_by);
+    assert!(caused_by < inner_tags);
+
+    // tags are only shown in the `Debug` report.
+    assert!(!err.to_string().contains("Tags:"));
+}
+
+#[test]
+fn prints_tag_names_in_alternate_debug() {
+    let err = oofs::oof!("custom error").tag::<RetryTag>();
+
+    let report = format!("{err:#?}");
+
+    assert!(report.contains("tags: [\n        \"tag_names::RetryTag\",\n    ]"));
+    assert!(!report.contains("TypeId"));
+}
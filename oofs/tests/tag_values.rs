@@ -0,0 +1,86 @@
+use oofs::{oofs, Oof, OofExt};
+use std::time::Duration;
+
+struct RetryTag;
+
+#[derive(Debug, PartialEq)]
+struct RetryAfter(Duration);
+
+#[derive(Debug, PartialEq)]
+struct HttpStatus(u16);
+
+#[oofs]
+fn outer() -> Result<(), Oof> {
+    inner()._tag_with(HttpStatus(503))?;
+
+    Ok(())
+}
+
+#[oofs]
+fn inner() -> Result<u64, Oof> {
+    let ret = "hello world"
+        .parse::<u64>()
+        ._tag_with(RetryAfter(Duration::from_secs(1)))
+        ._tag_with(HttpStatus(500))?;
+
+    Ok(ret)
+}
+
+#[test]
+fn carries_values_to_outer_layers() {
+    let err = outer().unwrap_err();
+
+    assert!(err.tagged::<HttpStatus>());
+    assert!(!err.tagged::<RetryAfter>());
+    assert!(err.tagged_nested::<RetryAfter>());
+
+    assert_eq!(err.tag_value::<HttpStatus>(), Some(&HttpStatus(503)));
+    assert_eq!(err.tag_value::<RetryAfter>(), None);
+    assert_eq!(
+        err.tag_value_nested::<RetryAfter>(),
+        Some(&RetryAfter(Duration::from_secs(1)))
+    );
+    // the outermost value comes first.
+    assert_eq!(err.tag_value_nested::<HttpStatus>(), Some(&HttpStatus(503)));
+}
+
+#[test]
+fn replaces_values_of_same_type() {
+    let err = oofs::oof!("custom error")
+        .tag::<HttpStatus>()
+        .tag_with(HttpStatus(400))
+        .tag_with(HttpStatus(404));
+
+    assert_eq!(err.tag_value::<HttpStatus>(), Some(&HttpStatus(404)));
+}
+
+#[oofs(tag(RetryTag, HttpStatus(502)))]
+fn tagged_by_attribute() -> Result<u64, Oof> {
+    let ret = "hello world".parse::<u64>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn tags_values_with_attribute() {
+    let err = tagged_by_attribute().unwrap_err();
+
+    assert!(err.tagged::<RetryTag>());
+    assert_eq!(err.tag_value::<HttpStatus>(), Some(&HttpStatus(502)));
+}
+
+#[test]
+fn tags_values_with_ensure() {
+    fn check(len: usize) -> Result<(), Oof> {
+        oofs::ensure!(len < 5, "too long", {
+            tag: [RetryTag, HttpStatus(400)]
+        });
+
+        Ok(())
+    }
+
+    let err = check(10).unwrap_err();
+
+    assert!(err.tagged::<RetryTag>());
+    assert_eq!(err.tag_value::<HttpStatus>(), Some(&HttpStatus(400)));
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "tracing")]
+
+use oofs::{oofs, Oof};
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+#[cfg(feature = "http")]
+use oofs::{oof, HttpMapping, HttpStatuses};
+#[cfg(feature = "http")]
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+#[oofs]
+fn outer(request_id: u64) -> Result<(), Oof> {
+    let span = tracing::info_span!("handle_request", request_id);
+    let _guard = span.enter();
+
+    inner("hello world")?;
+
+    Ok(())
+}
+
+#[oofs]
+#[tracing::instrument]
+fn inner(text: &str) -> Result<u64, Oof> {
+    let ret = text.parse::<u64>()?;
+
+    Ok(ret)
+}
+
+#[test]
+fn captures_span_trace() {
+    let subscriber = Registry::default().with(tracing_error::ErrorLayer::default());
+
+    let err = tracing::subscriber::with_default(subscriber, || outer(42).unwrap_err());
+
+    let span_trace = err.span_trace().expect("span trace should be captured");
+    let span_trace = span_trace.to_string();
+    assert!(span_trace.contains("handle_request"));
+    assert!(span_trace.contains("request_id=42"));
+    assert!(span_trace.contains("inner"));
+
+    let debug = format!("{err:?}");
+    assert_eq!(debug.matches("Span trace:").count(), 1);
+    assert!(debug.find("Span trace:").unwrap() > debug.find("Caused by:").unwrap());
+}
+
+#[test]
+fn skips_span_trace_without_error_layer() {
+    let err = outer(42).unwrap_err();
+
+    assert!(err.span_trace().is_none());
+    assert!(!format!("{err:?}").contains("Span trace:"));
+}
+
+#[cfg(feature = "http")]
+#[derive(Clone, Default)]
+struct Logs(Arc<Mutex<Vec<u8>>>);
+
+#[cfg(feature = "http")]
+impl io::Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn logs_http_errors_with_tracing() {
+    let logs = Logs::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    let statuses = HttpStatuses::new();
+    let err = oof!("db is down").attach("secret");
+    let public = statuses.public_error(&err);
+
+    tracing::subscriber::with_default(subscriber, || statuses.log(&err, &public));
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("ERROR"));
+    assert!(logs.contains("status=500"));
+    assert!(logs.contains("db is down"));
+    assert!(logs.contains("secret"));
+}
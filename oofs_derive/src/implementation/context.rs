@@ -1,4 +1,4 @@
-use super::props::Props;
+use super::props::{Props, TagArg};
 use proc_macro2::Span;
 use quote::{quote, quote_spanned, ToTokens};
 use syn::{
@@ -7,7 +7,6 @@ use syn::{
     token::Comma,
     token::{Await, Brace, Dot, Eq, Let, Paren, Semi},
     Expr, ExprAwait, ExprCall, ExprField, ExprMethodCall, Ident, Path, PathArguments, ReturnType,
-    Type,
 };
 
 pub struct Context<'a> {
@@ -147,17 +146,25 @@ impl<'a> ToTokens for ContextInner<'a> {
             braced.extend(quote_spanned!(span=> OofGenerator::build_oof));
             Paren(span).surround(braced, |parens| {
                 fn tag<'a>(
-                    mut tags: impl Iterator<Item = &'a Type>,
+                    mut tags: impl Iterator<Item = &'a TagArg>,
                     tokens: &mut proc_macro2::TokenStream,
                     f: impl FnOnce(&mut proc_macro2::TokenStream),
                 ) {
-                    if let Some(t) = tags.next() {
-                        tokens.extend(quote!(::oofs::OofExt::_tag::<#t>));
-                        Paren(t.span()).surround(tokens, |parens| {
-                            tag(tags, parens, f);
-                        });
-                    } else {
-                        f(tokens);
+                    match tags.next() {
+                        Some(TagArg::Type(t)) => {
+                            tokens.extend(quote!(::oofs::OofExt::_tag::<#t>));
+                            Paren(t.span()).surround(tokens, |parens| {
+                                tag(tags, parens, f);
+                            });
+                        }
+                        Some(TagArg::Value(v)) => {
+                            tokens.extend(quote!(::oofs::OofExt::_tag_with));
+                            Paren(v.span()).surround(tokens, |parens| {
+                                tag(tags, parens, f);
+                                parens.extend(quote!(, #v))
+                            });
+                        }
+                        None => f(tokens),
                     }
                 }
 
@@ -193,14 +200,74 @@ impl<'a> ToTokens for ContextInner<'a> {
                     }
                 }
 
-                attach_lazy(props.attach_lazy.iter().rev(), parens, |tokens| {
-                    attach(props.attach.iter().rev(), tokens, |tokens| {
-                        tag(props.tag.iter().rev(), tokens, |tokens| {
-                            receiver.write_call(tokens);
+                fn code(
+                    span: Span,
+                    code: Option<&Expr>,
+                    tokens: &mut proc_macro2::TokenStream,
+                    f: impl FnOnce(&mut proc_macro2::TokenStream),
+                ) {
+                    if let Some(c) = code {
+                        tokens.extend(quote_spanned!(span=> ::oofs::OofExt::_code));
+                        Paren(span).surround(tokens, |parens| {
+                            f(parens);
+                            parens.extend(quote!(, #c))
+                        });
+                    } else {
+                        f(tokens);
+                    }
+                }
+
+                fn severity(
+                    span: Span,
+                    severity: Option<&Expr>,
+                    tokens: &mut proc_macro2::TokenStream,
+                    f: impl FnOnce(&mut proc_macro2::TokenStream),
+                ) {
+                    if let Some(s) = severity {
+                        tokens.extend(quote_spanned!(span=> ::oofs::OofExt::_severity));
+                        Paren(span).surround(tokens, |parens| {
+                            f(parens);
+                            // variants can be given without the path, like `severity = Critical`.
+                            parens.extend(quote!(, {
+                                #[allow(unused_imports)]
+                                use ::oofs::Severity::*;
+                                #s
+                            }))
+                        });
+                    } else {
+                        f(tokens);
+                    }
+                }
 
-                            for method in chain {
-                                method.write_call(tokens);
-                            }
+                severity(span, props.severity.as_ref(), parens, |tokens| {
+                    code(span, props.code.as_ref(), tokens, |tokens| {
+                        attach_lazy(props.attach_lazy.iter().rev(), tokens, |tokens| {
+                            attach(props.attach.iter().rev(), tokens, |tokens| {
+                                tag(props.tag.iter().rev(), tokens, |tokens| {
+                                    // errors that cannot be the source of `Oof` as they are, are snapshotted
+                                    // before any meta method is called on them.
+                                    let first_meta =
+                                        chain.iter().position(|m| m.is_meta).unwrap_or(chain.len());
+                                    let (calls, metas) = chain.split_at(first_meta);
+
+                                    let mut res = proc_macro2::TokenStream::new();
+                                    receiver.write_call(&mut res);
+                                    for method in calls {
+                                        method.write_call(&mut res);
+                                    }
+
+                                    // `match` keeps the temporaries of the expression alive, as `?` would.
+                                    tokens.extend(quote_spanned! {span=>
+                                        match #res {
+                                            __res => (&&&&__res).__oof_kind().into_source(__res),
+                                        }
+                                    });
+
+                                    for method in metas {
+                                        method.write_call(tokens);
+                                    }
+                                });
+                            });
                         });
                     });
                 });
@@ -532,23 +599,24 @@ impl<'a> Method<'a> {
         expr: &'a ExprMethodCall,
         props: &'a Props,
     ) -> Self {
-        let prefix = format!("__{}", index);
+        // names of only underscores and digits, like `__1_0`, are linted by clippy in user crates.
+        let prefix = format!("__arg{}", index);
 
         let is_meta = expr.method.to_string().starts_with('_');
 
-        let args = (!is_meta)
-            .then(|| Arg::from_punctuated(&prefix, agg_index, &expr.args, props))
-            .unwrap_or_default();
+        let args = if !is_meta {
+            Arg::from_punctuated(&prefix, agg_index, &expr.args, props)
+        } else {
+            Default::default()
+        };
 
-        let this = Self {
+        Self {
             is_meta,
             args,
             dot_await: None,
             expr,
             props,
-        };
-
-        this
+        }
     }
 
     fn dot_await(&mut self, dot_token: &'a Dot, await_token: &'a Await) {
@@ -669,7 +737,7 @@ impl<'a> Arg<'a> {
             .map(|(i, a)| {
                 (
                     Arg::new(prefix, i, agg_index, a.value(), props),
-                    a.punct().map(|p| *p),
+                    a.punct().copied(),
                 )
             })
             .collect()
@@ -701,10 +769,16 @@ impl<'a> Arg<'a> {
 
         let skip = props.debug_skip.contains(expr);
 
-        let debug_method = props
-            .debug_with
-            .iter()
-            .find(|v| &v.arg == *expr)
+        let debug_with = props.debug_with.iter().find(|v| &v.arg == *expr);
+
+        // primitive values are only snapshotted for the default debug method;
+        // custom debug methods are called when the error is built.
+        let try_lazy = match debug_with {
+            Some(_) => quote!(try_lazy),
+            None => quote!(try_lazy_debug),
+        };
+
+        let debug_method = debug_with
             .map(|d| quote!(Some(#d)))
             .unwrap_or_else(|| quote!(v.try_debug_fmt()));
 
@@ -713,7 +787,7 @@ impl<'a> Arg<'a> {
         tokens.extend(quote! {
             let #arg_type = type_name_of_val(&#arg);
             let #arg_wrapper = __VarWrapper(#arg);
-            let #arg_lazy_exec = #arg_wrapper.try_lazy(!#skip && (#debug_non_copyable || #arg_wrapper.impls_copy()), |v| #debug_method);
+            let #arg_lazy_exec = #arg_wrapper.#try_lazy(!#skip && (#debug_non_copyable || #arg_wrapper.impls_copy()), |v| #debug_method);
             let #arg = #arg_wrapper.into_inner();
         });
     }
@@ -765,7 +839,7 @@ fn fmt_expr(f: &mut String, func: &Expr) {
         }
         Binary(e) => {
             fmt_expr(f, &e.left);
-            *f += &format!(" {} ", e.op.to_token_stream().to_string());
+            *f += &format!(" {} ", e.op.to_token_stream());
             fmt_expr(f, &e.right);
         }
         Box(e) => {
@@ -775,7 +849,7 @@ fn fmt_expr(f: &mut String, func: &Expr) {
         Break(e) => {
             *f += "break ";
             if let Some(expr) = &e.expr {
-                fmt_expr(f, &expr);
+                fmt_expr(f, expr);
             }
         }
         Call(e) => {
@@ -892,7 +966,7 @@ fn fmt_expr(f: &mut String, func: &Expr) {
         Array(e) => {
             *f += "[";
             for pair in e.elems.pairs() {
-                fmt_expr(f, &pair.value());
+                fmt_expr(f, pair.value());
                 if pair.punct().is_some() {
                     *f += ", ";
                 }
@@ -918,7 +992,7 @@ fn fmt_expr(f: &mut String, func: &Expr) {
         Tuple(e) => {
             *f += "(";
             for pair in e.elems.pairs() {
-                fmt_expr(f, &pair.value());
+                fmt_expr(f, pair.value());
                 if pair.punct().is_some() {
                     *f += ", ";
                 }
@@ -942,7 +1016,7 @@ fn fmt_expr(f: &mut String, func: &Expr) {
         Yield(e) => {
             *f += "yield ";
             if let Some(expr) = &e.expr {
-                fmt_expr(f, &expr);
+                fmt_expr(f, expr);
             }
         }
         _ => *f += "_",
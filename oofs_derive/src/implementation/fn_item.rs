@@ -47,7 +47,7 @@ impl ToTokens for OofFn {
         if props.skip() {
             block.to_tokens(tokens);
         } else {
-            props.write(tokens).block(block);
+            props.write(tokens).fn_block(sig, block);
         }
     }
 }
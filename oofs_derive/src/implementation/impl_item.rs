@@ -42,7 +42,7 @@ impl ToTokens for OofImpl {
         let mut impl_props = self.props.clone();
 
         for attr in attrs {
-            if !impl_props.merge_attr(&attr) {
+            if !impl_props.merge_attr(attr) {
                 attr.to_tokens(tokens);
             }
         }
@@ -74,7 +74,7 @@ impl ToTokens for OofImpl {
 
                     let mut attr_exists = false;
                     for attr in attrs {
-                        if fn_props.merge_attr(&attr) {
+                        if fn_props.merge_attr(attr) {
                             attr_exists = true;
                         }
                         attr.to_tokens(braces);
@@ -87,7 +87,7 @@ impl ToTokens for OofImpl {
                     if fn_props.skip() || !(attr_exists || returns_result(sig)) {
                         block.to_tokens(braces);
                     } else {
-                        fn_props.write(braces).block(block);
+                        fn_props.write(braces).fn_block(sig, block);
                     }
                 } else {
                     item.to_tokens(braces);
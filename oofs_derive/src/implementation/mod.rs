@@ -5,9 +5,11 @@ mod context;
 mod fn_item;
 mod impl_item;
 mod props;
+mod tag;
 mod write;
 
 pub use props::*;
+pub use tag::TagDerive;
 
 // TODO FEATURES:
 // - pre-check. ex) #[oof(pre(!list.len().is_empty()))], ex) #[oof(pre(!list.len().is_empty(), message = "custom message"))]
@@ -8,14 +8,14 @@ use std::{
 };
 use syn::{
     buffer::Cursor, parenthesized, parse::Parse, punctuated::Punctuated, token::Paren, Attribute,
-    Expr, Ident, LitBool, Token, Type,
+    Expr, Ident, LitBool, PathArguments, Token, Type,
 };
 
 pub fn props() -> Props {
     Props::default()
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Props {
     pub args: PropArgs,
 }
@@ -40,14 +40,6 @@ impl DerefMut for Props {
     }
 }
 
-impl Default for Props {
-    fn default() -> Self {
-        Self {
-            args: Default::default(),
-        }
-    }
-}
-
 impl Props {
     pub fn write<'a>(&'a self, tokens: &'a mut proc_macro2::TokenStream) -> Writer<'a> {
         Writer::new(tokens, self)
@@ -206,28 +198,89 @@ impl_prop_args! {
     closures: bool as option,
     async_blocks: bool as option,
     skip: bool as option,
-    tag: Type as vec,
+    catch_panics: bool as option,
+    tag: TagArg as vec,
     attach: Expr as vec,
     attach_lazy: Expr as vec,
     debug_skip: Expr as vec,
     debug_with: DebugWith as vec,
     debug_non_copyable: DebugNonCopyable,
+    code: Expr as option,
+    severity: Expr as option,
+}
+
+/// Tag type like `MyTag`, or tag value like `HttpStatus(503)`.
+#[derive(Clone)]
+pub enum TagArg {
+    Type(Type),
+    Value(Expr),
+}
+
+impl Parse for TagArg {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ahead = input.fork();
+
+        if let Ok(ty) = ahead.parse::<Type>() {
+            // `HttpStatus(503)` is not a type, but `Value(Unit)` would parse as a `Fn`-like type path.
+            let is_call = match &ty {
+                Type::Path(p) => p
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|s| matches!(s.arguments, PathArguments::Parenthesized(_))),
+                _ => false,
+            };
+
+            if !is_call && (ahead.is_empty() || ahead.peek(Token![,])) {
+                return input.parse().map(Self::Type);
+            }
+        }
+
+        input.parse().map(Self::Value)
+    }
+}
+
+/// Input of `__tags!((oof) MyTag, HttpStatus(503))`, used by `ensure!`.
+pub struct TagChain {
+    oof: Expr,
+    tags: Punctuated<TagArg, Token!(,)>,
+}
+
+impl Parse for TagChain {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        parenthesized!(content in input);
+
+        Ok(Self {
+            oof: content.parse()?,
+            tags: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl ToTokens for TagChain {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let oof = &self.oof;
+        tokens.extend(quote!((#oof)));
+
+        for tag in &self.tags {
+            match tag {
+                TagArg::Type(t) => tokens.extend(quote!(.tag::<#t>())),
+                TagArg::Value(v) => tokens.extend(quote!(.tag_with(#v))),
+            }
+        }
+    }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 pub enum DebugNonCopyable {
     Full,
     Disabled,
+    #[default]
     None,
     // CloneLazy,
 }
 
-impl Default for DebugNonCopyable {
-    fn default() -> Self {
-        DebugNonCopyable::None
-    }
-}
-
 impl ToTokens for DebugNonCopyable {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         use DebugNonCopyable::*;
@@ -290,12 +343,10 @@ fn get_arg(cursor: &mut Cursor, recv: &mut TokenStream) {
     while let Some((tt, next)) = cursor.token_tree() {
         if let TokenTree::Punct(p) = &tt {
             if p.as_char() == '-' && p.spacing() == Spacing::Joint {
-                if let Some((tt2, next2)) = next.token_tree() {
-                    if let TokenTree::Punct(p2) = &tt2 {
-                        if p2.as_char() == '>' && p2.spacing() == Spacing::Alone {
-                            *cursor = next2;
-                            return;
-                        }
+                if let Some((TokenTree::Punct(p2), next2)) = next.token_tree() {
+                    if p2.as_char() == '>' && p2.spacing() == Spacing::Alone {
+                        *cursor = next2;
+                        return;
                     }
                 }
             }
@@ -324,21 +375,19 @@ fn get_method(cursor: &mut Cursor, recv: &mut TokenStream) {
                     *cursor = next;
                     continue;
                 }
-                TokenTree::Punct(p) => {
-                    if p.as_char() == find.0 {
-                        if let Some((tt2, next2)) = next.token_tree() {
-                            if let TokenTree::Ident(i) = &tt2 {
-                                if i == find.1 {
-                                    recv.extend(once(replace.clone()));
-                                    *cursor = next2;
-                                    found = true;
-                                    continue;
-                                }
+                TokenTree::Punct(p) if p.as_char() == find.0 => {
+                    if let Some((tt2, next2)) = next.token_tree() {
+                        if let TokenTree::Ident(i) = &tt2 {
+                            if i == find.1 {
+                                recv.extend(once(replace.clone()));
+                                *cursor = next2;
+                                found = true;
+                                continue;
                             }
-
-                            recv.extend([tt, tt2]);
-                            continue;
                         }
+
+                        recv.extend([tt, tt2]);
+                        continue;
                     }
                 }
                 _ => {}
@@ -373,21 +422,19 @@ fn find_and_replace(
                 let g: TokenTree = Group::new(g.delimiter(), stream).into();
                 ret.extend(once(g));
             }
-            TokenTree::Punct(p) => {
-                if p.as_char() == find.0 {
-                    if let Some(tt2) = tokens.next() {
-                        if let TokenTree::Ident(i) = &tt2 {
-                            if i == find.1 {
-                                ret.extend(once(replace.clone()));
-                                ret.extend(tokens);
-                                found = true;
-                                break;
-                            }
+            TokenTree::Punct(p) if p.as_char() == find.0 => {
+                if let Some(tt2) = tokens.next() {
+                    if let TokenTree::Ident(i) = &tt2 {
+                        if i == find.1 {
+                            ret.extend(once(replace.clone()));
+                            ret.extend(tokens);
+                            found = true;
+                            break;
                         }
-
-                        ret.extend([tt, tt2]);
-                        continue;
                     }
+
+                    ret.extend([tt, tt2]);
+                    continue;
                 }
             }
             _ => {}
@@ -427,6 +474,19 @@ fn extract_generic<T: Default + Parse>(input: syn::parse::ParseStream) -> syn::R
     Ok(t)
 }
 
+fn extract_optional<T: Parse>(input: syn::parse::ParseStream) -> syn::Result<Option<T>> {
+    // both `code = "E-001"` and `code("E-001")` are accepted.
+    if input.peek(Token![=]) {
+        input.parse::<Token![=]>()?;
+        return input.parse().map(Some);
+    }
+
+    let content;
+    parenthesized!(content in input);
+
+    content.parse().map(Some)
+}
+
 fn extract_vec<T: Parse>(input: syn::parse::ParseStream) -> syn::Result<Vec<T>> {
     let content;
     parenthesized!(content in input);
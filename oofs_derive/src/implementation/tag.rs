@@ -0,0 +1,129 @@
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
+use syn::{
+    parenthesized, parse::Parse, punctuated::Punctuated, DeriveInput, Ident, LitStr, Token, Type,
+};
+
+/// `#[derive(Tag)]` with optional `#[tag(parent(...), name = "...", description = "...", severity = ...)]`.
+pub struct TagDerive {
+    ident: Ident,
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+    severity: Option<Ident>,
+    parents: Vec<Type>,
+}
+
+enum TagMeta {
+    Parent(Punctuated<Type, Token!(,)>),
+    Name(LitStr),
+    Description(LitStr),
+    Severity(Ident),
+}
+
+impl Parse for TagMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+
+        if ident == "parent" {
+            let content;
+            parenthesized!(content in input);
+
+            return Ok(Self::Parent(Punctuated::parse_terminated(&content)?));
+        }
+
+        input.parse::<Token!(=)>()?;
+
+        if ident == "name" {
+            Ok(Self::Name(input.parse()?))
+        } else if ident == "description" {
+            Ok(Self::Description(input.parse()?))
+        } else if ident == "severity" {
+            Ok(Self::Severity(input.parse()?))
+        } else {
+            abort!(
+                ident,
+                "Expected one of `parent`, `name`, `description`, `severity`"
+            );
+        }
+    }
+}
+
+impl Parse for TagDerive {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let DeriveInput {
+            attrs,
+            ident,
+            generics,
+            ..
+        } = input.parse()?;
+
+        if !generics.params.is_empty() {
+            abort!(generics, "`Tag` cannot be derived for generic types");
+        }
+
+        let mut tag = Self {
+            ident,
+            name: None,
+            description: None,
+            severity: None,
+            parents: Vec::new(),
+        };
+
+        for attr in attrs.iter().filter(|a| a.path.is_ident("tag")) {
+            let metas = attr.parse_args_with(Punctuated::<TagMeta, Token!(,)>::parse_terminated)?;
+
+            for meta in metas {
+                match meta {
+                    TagMeta::Parent(p) => tag.parents.extend(p),
+                    TagMeta::Name(n) => tag.name = Some(n),
+                    TagMeta::Description(d) => tag.description = Some(d),
+                    TagMeta::Severity(s) => tag.severity = Some(s),
+                }
+            }
+        }
+
+        Ok(tag)
+    }
+}
+
+impl ToTokens for TagDerive {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self {
+            ident,
+            name,
+            description,
+            severity,
+            parents,
+        } = self;
+
+        let name = match name {
+            Some(name) => name.to_token_stream(),
+            None => quote!(stringify!(#ident)),
+        };
+
+        let description = match description {
+            Some(d) => quote!(::core::option::Option::Some(#d)),
+            None => quote!(::core::option::Option::None),
+        };
+
+        let severity = match severity {
+            Some(s) => quote!(::core::option::Option::Some(::oofs::Severity::#s)),
+            None => quote!(::core::option::Option::None),
+        };
+
+        tokens.extend(quote! {
+            impl ::oofs::Tag for #ident {
+                const NAME: &'static str = #name;
+                const DESCRIPTION: ::core::option::Option<&'static str> = #description;
+                const SEVERITY: ::core::option::Option<::oofs::Severity> = #severity;
+                const PARENTS: &'static [fn() -> ::core::any::TypeId] = &[
+                    #(::core::any::TypeId::of::<#parents>),*
+                ];
+            }
+
+            ::oofs::__used_by_attribute::inventory::submit! {
+                ::oofs::TagInfo::of::<#ident>()
+            }
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use super::props::Props;
-use quote::ToTokens;
+use proc_macro_error::abort;
+use quote::{quote, ToTokens};
 use syn::{token::Semi, *};
 
 pub struct Writer<'a> {
@@ -12,6 +13,40 @@ impl<'a> Writer<'a> {
         Self { tokens, props }
     }
 
+    /// Body of an instrumented `fn`.
+    pub fn fn_block(self, sig: &Signature, block: &Block) {
+        let Self { tokens, props } = self;
+
+        if !props.catch_panics() {
+            return props.write(tokens).block(block);
+        }
+
+        if let Some(asyncness) = &sig.asyncness {
+            abort!(
+                asyncness,
+                "`catch_panics` cannot be used on async functions"
+            );
+        }
+
+        let mut body = proc_macro2::TokenStream::new();
+        props.write(&mut body).block(block);
+
+        // the return type lets the body coerce its values, like `&String` to `&str`, as the function would;
+        // closures cannot return `impl Trait`, so it is inferred from the function instead.
+        let output = match &sig.output {
+            ReturnType::Type(arrow, ty) if !has_impl_trait(ty.to_token_stream()) => {
+                quote!(#arrow #ty)
+            }
+            _ => quote!(),
+        };
+
+        tokens.extend(quote! {
+            {
+                ::oofs::__used_by_attribute::__catch_panics(move || #output #body)
+            }
+        });
+    }
+
     pub fn block(self, block: &Block) {
         let Self { tokens, props } = self;
 
@@ -820,3 +855,12 @@ fn _is_generic_ok(expr: &Expr) -> bool {
 
     false
 }
+
+/// Whether the type has `impl Trait` anywhere, like `Result<impl Iterator<Item = u8>, Oof>`.
+fn has_impl_trait(ty: proc_macro2::TokenStream) -> bool {
+    ty.into_iter().any(|tt| match tt {
+        proc_macro2::TokenTree::Ident(i) => i == "impl",
+        proc_macro2::TokenTree::Group(g) => has_impl_trait(g.stream()),
+        _ => false,
+    })
+}
@@ -13,7 +13,10 @@ mod implementation;
 /// - [tag](#tag)
 /// - [attach](#attach)
 /// - [attach_lazy](#attach_lazy)
+/// - [code](#code)
+/// - [severity](#severity)
 /// - [skip](#skip)
+/// - [catch_panics](#catch_panics)
 /// - [closures](#closures)
 /// - [async_blocks](#async_blocks)
 /// - [debug_skip](#debug_skip)
@@ -77,7 +80,10 @@ mod implementation;
 ///             some_async_fn().await?;
 ///
 ///             Ok::<_, Oof>(())
-///         }.await?; // <- context injected here
+///         };
+///
+///         // context is injected here
+///         a.await?;
 ///
 ///         // ...
 ///         # Ok(0)
@@ -184,6 +190,9 @@ mod implementation;
 ///
 /// This argument tags specified types into all `?` operators.
 ///
+/// Values like `tag(HttpStatus(503))` are also accepted; they are tagged with `_tag_with`,
+/// and can be retrieved with `tag_value::<HttpStatus>()`.
+///
 /// Ex)
 /// ```rust
 /// use oofs::{oofs, Oof};
@@ -285,6 +294,57 @@ mod implementation;
 /// }
 /// ```
 ///
+/// ## code
+///
+/// `#[oofs(code = "E-STORAGE-042")]` or `#[oofs(code("E-STORAGE-042"))]`
+///
+/// This argument sets the error code of all `?` operators; see [Oof::code](../oofs/struct.Oof.html#method.code).
+///
+/// An argument on a method overrides the one on its `impl` block.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oofs, Oof};
+///
+/// pub struct Storage;
+/// # fn read_block() -> Result<(), Oof> { todo!() }
+///
+/// #[oofs(code = "E-STORAGE-000")]
+/// impl Storage {
+///     #[oofs(code = "E-STORAGE-042")]
+///     fn read(&self) -> Result<(), Oof> {
+///         // error code is `E-STORAGE-042`
+///         read_block()?;
+///
+///         // ...
+///         # Ok(())
+///     }
+/// }
+/// ```
+///
+/// ## severity
+///
+/// `#[oofs(severity = Critical)]` or `#[oofs(severity(Severity::Critical))]`
+///
+/// This argument sets the severity of all `?` operators; see [Oof::severity](../oofs/struct.Oof.html#method.severity).
+///
+/// Variants of `Severity` can be given without the path.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oofs, Oof};
+/// # fn read_block() -> Result<(), Oof> { todo!() }
+///
+/// #[oofs(code = "E-STORAGE-042", severity = Critical)]
+/// fn read() -> Result<(), Oof> {
+///     // error code is `E-STORAGE-042`, and severity is `Critical`
+///     read_block()?;
+///
+///     // ...
+///     # Ok(())
+/// }
+/// ```
+///
 /// ## skip
 ///
 /// `#[oofs(skip)]` or `#[skip(true)]`
@@ -323,6 +383,32 @@ mod implementation;
 /// }
 /// ```
 ///
+/// ## catch_panics
+///
+/// `#[oofs(catch_panics)]` or `#[oofs(catch_panics(true))]`
+///
+/// `#[oofs(catch_panics(false))]` will stop catching panics, if already enabled from outer scope.
+///
+/// This argument converts panics inside the function into an `Oof` with the panic message and its location,
+/// tagged as `Panic`; see [catch_unwind](../oofs/fn.catch_unwind.html).
+///
+/// The function body is not required to be `UnwindSafe`, and this argument cannot be used on `async fn`.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oofs, Oof, Panic};
+///
+/// #[oofs(catch_panics)]
+/// fn divide(a: u64, b: u64) -> Result<u64, Oof> {
+///     Ok(a / b)
+/// }
+///
+/// let err = divide(1, 0).unwrap_err();
+///
+/// assert!(err.tagged::<Panic>());
+/// assert!(err.to_string().starts_with("panicked: attempt to divide by zero"));
+/// ```
+///
 /// ## closures
 ///
 /// `#[oofs(closures)]` or `#[oofs(closures(true))]`
@@ -530,3 +616,49 @@ pub fn oofs(
     let args = parse_macro_input!(args as implementation::PropArgs);
     oofs.with_args(args).to_token_stream().into()
 }
+
+/// Implement `oofs::Tag` and register the tag, so that `tagged::<P>()` matches it for each of its ancestors `P`.
+///
+/// Parents and metadata are declared with `#[tag(...)]`:
+///
+/// - `parent(Transient, Network)`: direct parent tags; any `'static` type can be a parent.
+/// - `name = "connection reset"`: display name of the tag; defaults to the type's name.
+/// - `description = "..."`: description of the tag.
+/// - `severity = Warning`: one of `oofs::Severity` variants.
+///
+/// Ex)
+/// ```rust
+/// use oofs::{oof, Tag};
+///
+/// #[derive(Tag)]
+/// struct Retryable;
+///
+/// #[derive(Tag)]
+/// #[tag(parent(Retryable), description = "error that may succeed on retry")]
+/// struct Transient;
+///
+/// #[derive(Tag)]
+/// #[tag(parent(Transient), name = "connection reset", severity = Warning)]
+/// struct ConnectionReset;
+///
+/// let err = oof!("custom error").tag::<ConnectionReset>();
+///
+/// assert!(err.tagged::<ConnectionReset>());
+/// assert!(err.tagged::<Transient>());
+/// assert!(err.tagged::<Retryable>());
+/// ```
+#[proc_macro_derive(Tag, attributes(tag))]
+#[proc_macro_error]
+pub fn derive_tag(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as implementation::TagDerive)
+        .to_token_stream()
+        .into()
+}
+
+#[doc(hidden)]
+#[proc_macro]
+pub fn __tags(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    parse_macro_input!(input as implementation::TagChain)
+        .to_token_stream()
+        .into()
+}
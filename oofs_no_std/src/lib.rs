@@ -0,0 +1,67 @@
+//! `no_std` crate using `#[oofs]`, to check that the generated code and the runtime only need `alloc`.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{borrow::ToOwned, string::String};
+use core::num::ParseIntError;
+use oofs::{ensure, oof, oofs, Oof, OofExt};
+
+/// Marker type used for tagging.
+pub struct RetryTag;
+
+#[derive(Debug)]
+pub struct Sensor {
+    pub raw: String,
+}
+
+#[oofs]
+pub fn read_sensor(raw: &str) -> Result<u64, Oof> {
+    let sensor = Sensor {
+        raw: raw.to_owned(),
+    };
+
+    let value = sensor.parse(checked_offset(1)?)?;
+
+    Ok(value)
+}
+
+#[oofs]
+pub fn checked_offset(offset: u64) -> Result<u64, Oof> {
+    ensure!(offset < 10, "offset out of range", {
+        tag: [RetryTag],
+        attach: [offset]
+    });
+
+    Ok(offset)
+}
+
+#[oofs]
+impl Sensor {
+    fn parse(&self, offset: u64) -> Result<u64, Oof> {
+        let ret = self
+            .raw
+            .parse::<u64>()
+            ._tag::<RetryTag>()
+            ._attach(offset)
+            ._attach_lazy(|| "while parsing the sensor value")?;
+
+        Ok(ret + offset)
+    }
+}
+
+pub fn parse_foreign(raw: &str) -> Result<u64, ParseIntError> {
+    raw.parse()
+}
+
+#[oofs]
+pub fn read_foreign(raw: &str) -> Result<u64, Oof> {
+    let ret = parse_foreign(raw)?;
+
+    Ok(ret)
+}
+
+pub fn custom() -> Oof {
+    oof!("sensor {} is offline", 3)
+}
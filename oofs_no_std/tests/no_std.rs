@@ -0,0 +1,36 @@
+use oofs_no_std::{checked_offset, custom, read_foreign, read_sensor, RetryTag};
+use std::error::Error;
+
+#[test]
+fn generates_context_without_std() {
+    let err = read_sensor("hello world").unwrap_err();
+
+    assert!(err.tagged_nested::<RetryTag>());
+
+    let debug = format!("{err:?}");
+    assert!(debug.contains("sensor.parse($0) failed"));
+    assert!(debug.contains("self.raw.parse() failed"));
+    assert!(debug.contains("while parsing the sensor value"));
+    assert!(debug.contains("Caused by:"));
+}
+
+#[test]
+fn returns_ok_without_std() {
+    assert_eq!(read_sensor("41").unwrap(), 42);
+}
+
+#[test]
+fn ensures_without_std() {
+    let err = checked_offset(12).unwrap_err();
+
+    assert!(err.tagged::<RetryTag>());
+    assert!(err.to_string().contains("offset out of range"));
+}
+
+#[test]
+fn wraps_foreign_errors_without_std() {
+    let err = read_foreign("x").unwrap_err();
+
+    assert!(err.source().is_some());
+    assert!(custom().to_string().starts_with("sensor 3 is offline"));
+}